@@ -1,7 +1,7 @@
 use eframe::{Frame, NativeOptions};
 use egui::Context;
 
-use egui_memory_editor::MemoryEditor;
+use egui_memory_editor::{Address, MemoryEditor, MemorySource};
 
 use crate::frame_history::FrameHistory;
 
@@ -31,8 +31,7 @@ impl Default for App {
             .with_address_range("All", 0..0xFFFF)
             .with_address_range("IO", 0xFF00..0xFF80)
             .with_window_title("Hello Editor!");
-        // At the moment the UI can handle addresses in the range from 0..2^(24 + log_2(column_count)).
-        // This is something that'll hopefully be addressed soon to allow for ranges up to 2^64.
+        // Ranges can span the entire address space, e.g. `0..Address::MAX`.
 
         // You can set the column count in the UI, but also here. There are a variety of options available in mem_editor.options
         mem_editor.options.column_count = 16;
@@ -50,15 +49,10 @@ impl eframe::App for App {
     fn update(&mut self, ctx: &Context, frame: &mut Frame) {
         create_frame_history(ctx, frame, &mut self.fh);
 
-        // This will automatically check for `mem_editor.options.is_open`, so no need to do that here.
-        // The write function is optional, if you don't set it the UI will be in read-only mode.
-        self.mem_editor.window_ui(
-            ctx,
-            &mut self.is_open,
-            &mut self.memory,
-            |mem, address| mem.read_value(address).into(),
-            |mem, address, val| mem.write_value(address, val),
-        );
+        // `Memory` implements `MemorySource`, alternatively `window_ui` can be used with a read and write closure.
+        // Addresses for which `MemorySource::is_writable` returns `false` will be displayed in read-only mode.
+        self.mem_editor
+            .window_ui_with_source(ctx, &mut self.is_open, &mut self.memory);
         // If your memory changes between frames you'll need to re-render at whatever framerate you want.
         ctx.request_repaint();
     }
@@ -86,6 +80,20 @@ impl Memory {
     }
 }
 
+impl MemorySource for Memory {
+    fn read(&mut self, address: Address) -> Option<u8> {
+        Some(self.read_value(address as usize))
+    }
+
+    fn write(&mut self, address: Address, value: u8) {
+        self.write_value(address as usize, value)
+    }
+
+    fn is_writable(&self, address: Address) -> bool {
+        address < self.memory.len() as Address
+    }
+}
+
 fn create_frame_history(ctx: &Context, frame: &Frame, frame_history: &mut FrameHistory) {
     frame_history.on_new_frame(ctx.input(|i| i.time), frame.info().cpu_usage);
     egui::SidePanel::left("SidePanel").show(ctx, |ui| {
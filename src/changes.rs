@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::memory_cache::MemoryCache;
+use crate::Address;
+
+/// Keeps track of which visible values changed recently, so that they can be highlighted.
+///
+/// Every frame the visible rows are compared against a snapshot of the same rows from the previous frame. Rows which
+/// just scrolled into view don't have a snapshot yet, and are never highlighted.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ChangeTracker {
+    address_space: Range<Address>,
+    row_length: usize,
+    /// The values of every visible row during the previous frame, by the start address of the row.
+    snapshot: HashMap<Address, Vec<Option<u8>>>,
+    /// The time at which an address last changed, in seconds.
+    changed_at: HashMap<Address, f64>,
+}
+
+impl ChangeTracker {
+    /// Compare the `visible_range` in the `memory_cache` with the previous frame, and take a new snapshot.
+    ///
+    /// Changes older than `duration` are forgotten.
+    pub fn update(
+        &mut self,
+        memory_cache: &MemoryCache,
+        address_space: &Range<Address>,
+        visible_range: &Range<Address>,
+        row_length: usize,
+        now: f64,
+        duration: f32,
+    ) {
+        let row_length = row_length.max(1);
+
+        // A different layout means the snapshot rows don't line up anymore.
+        if self.address_space != *address_space || self.row_length != row_length {
+            self.address_space = address_space.clone();
+            self.row_length = row_length;
+            self.snapshot.clear();
+            self.changed_at.clear();
+        }
+
+        let mut snapshot = HashMap::with_capacity(self.snapshot.len());
+
+        for row_start in visible_range.clone().step_by(row_length) {
+            let row_end = row_start.saturating_add(row_length as Address).min(visible_range.end);
+            let values: Vec<Option<u8>> = (row_start..row_end).map(|address| memory_cache.get(address)).collect();
+
+            if let Some(previous) = self.snapshot.get(&row_start) {
+                let changes = (row_start..row_end).zip(previous.iter().zip(&values));
+
+                for (address, (previous, current)) in changes {
+                    // Values which couldn't be read aren't changes.
+                    if matches!((previous, current), (Some(previous), Some(current)) if previous != current) {
+                        self.changed_at.insert(address, now);
+                    }
+                }
+            }
+
+            snapshot.insert(row_start, values);
+        }
+
+        self.snapshot = snapshot;
+        self.changed_at
+            .retain(|_, changed_at| now - *changed_at < duration as f64);
+    }
+
+    /// How recently `address` changed, from `1.0` for a change in this frame down to `0.0` after `duration` seconds.
+    /// Returns `None` if it didn't change recently.
+    pub fn intensity(&self, address: Address, now: f64, duration: f32) -> Option<f32> {
+        let changed_at = self.changed_at.get(&address)?;
+        let elapsed = (now - changed_at) as f32;
+
+        (elapsed < duration).then(|| 1.0 - elapsed / duration)
+    }
+
+    /// Whether any change is still fading out, in which case the UI has to keep repainting.
+    pub fn is_fading(&self) -> bool {
+        !self.changed_at.is_empty()
+    }
+
+    /// Forget all changes and snapshots, e.g. when change highlighting is disabled.
+    pub fn clear(&mut self) {
+        self.snapshot.clear();
+        self.changed_at.clear();
+    }
+}
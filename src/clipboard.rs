@@ -0,0 +1,214 @@
+use std::fmt::Write;
+use std::ops::Range;
+
+use crate::option_data::{CopyFormat, MemoryEditorOptions};
+use crate::utilities::{address_characters, ascii_char, to_base64};
+use crate::Address;
+
+/// Copies are formatted in memory, so they're limited to this many bytes.
+pub(crate) const MAX_COPY_LENGTH: usize = 16 * 1024 * 1024;
+
+/// Format the `bytes` starting at `start` for the clipboard.
+///
+/// `None` values are written as the [`MemoryEditorOptions::none_display_value`] where the format allows it, and as
+/// `0` otherwise.
+pub(crate) fn format_bytes(
+    format: CopyFormat,
+    start: Address,
+    bytes: &[Option<u8>],
+    address_space: &Range<Address>,
+    options: &MemoryEditorOptions,
+) -> String {
+    let hex = |value: &Option<u8>| match value {
+        Some(value) => format!("{:02X}", value),
+        None => options.none_display_value.clone(),
+    };
+    let values = || bytes.iter().map(|value| value.unwrap_or(0));
+
+    match format {
+        CopyFormat::HexSpaced => bytes.iter().map(hex).collect::<Vec<_>>().join(" "),
+        CopyFormat::HexContiguous => bytes.iter().map(hex).collect(),
+        CopyFormat::HexDump => hex_dump(start, bytes, address_space, options),
+        CopyFormat::CArray => format!(
+            "unsigned char data[{}] = {{\n{}\n}};",
+            bytes.len(),
+            array_body(values(), options.column_count)
+        ),
+        CopyFormat::RustArray => format!(
+            "let data: [u8; {}] = [\n{}\n];",
+            bytes.len(),
+            array_body(values(), options.column_count)
+        ),
+        CopyFormat::Base64 => to_base64(&values().collect::<Vec<_>>()),
+        CopyFormat::Ascii => values().map(ascii_char).collect(),
+    }
+}
+
+/// Indented, comma separated `0x00` literals with `per_line` values on every line.
+fn array_body(values: impl Iterator<Item = u8>, per_line: usize) -> String {
+    let literals = values.map(|value| format!("{:#04X}", value)).collect::<Vec<_>>();
+
+    literals
+        .chunks(per_line.max(1))
+        .map(|line| format!("    {}", line.join(", ")))
+        .collect::<Vec<_>>()
+        .join(",\n")
+}
+
+/// Lay out the bytes in rows just like the main UI does, with the address on the left and the ASCII on the right.
+fn hex_dump(
+    start: Address,
+    bytes: &[Option<u8>],
+    address_space: &Range<Address>,
+    options: &MemoryEditorOptions,
+) -> String {
+    let column_count = options.row_length();
+    let address_characters = address_characters(address_space);
+    let cell_width = options.none_display_value.chars().count().max(2);
+    let end = start.saturating_add(bytes.len() as Address);
+    let mut result = String::new();
+
+    // Rows are aligned to the start of the address space, same as in the UI. Bytes from outside of the address space
+    // are aligned to their own start instead.
+    let mut row_start = match start.checked_sub(address_space.start) {
+        Some(offset) => address_space.start + offset / column_count as Address * column_count as Address,
+        None => start,
+    };
+
+    while row_start < end {
+        let _ = write!(result, "0x{:01$X}:", row_start, address_characters);
+        let mut ascii = String::with_capacity(column_count);
+
+        for column in 0..column_count {
+            // Every 8 bytes is one 'sub-column' in the UI.
+            if column != 0 && column % 8 == 0 {
+                result.push(' ');
+            }
+            result.push(' ');
+
+            let address = row_start.saturating_add(column as Address);
+            if (start..end).contains(&address) {
+                let value = bytes[(address - start) as usize];
+                match value {
+                    Some(value) => {
+                        let _ = write!(result, "{:<1$}", format!("{:02X}", value), cell_width);
+                    }
+                    None => result.push_str(&options.none_display_value),
+                }
+                ascii.push(ascii_char(value.unwrap_or(0)));
+            } else {
+                result.push_str(&" ".repeat(cell_width));
+                ascii.push(' ');
+            }
+        }
+
+        let _ = writeln!(result, "  |{}|", ascii);
+        row_start = row_start.saturating_add(column_count as Address);
+
+        if row_start == Address::MAX {
+            break;
+        }
+    }
+
+    result.pop();
+    result
+}
+
+/// Parse pasted text as hex bytes.
+///
+/// Accepts hex with or without separators (`DE AD`, `DEAD`, `DE,AD`), `0x` prefixes and C/Rust array syntax
+/// (`{ 0xDE, 0xAD }`, `[0xDEu8, 0xAD]`). A prefixed number is split into bytes like any other contiguous hex, so
+/// `0xDEAD` is `DE AD`. Returns `None` if the text isn't valid hex.
+pub(crate) fn parse_hex(text: &str) -> Option<Vec<u8>> {
+    // Only consider the contents of an array literal, ignoring any declaration in front of it.
+    let text = text.rsplit_once('=').map_or(text, |(_, value)| value);
+    let text = text.trim().trim_end_matches(';').trim_end();
+    let text = text
+        .strip_prefix(['{', '['])
+        .and_then(|text| text.strip_suffix(['}', ']']))
+        .unwrap_or(text);
+
+    let mut result = Vec::new();
+
+    for token in text.split(|c: char| c.is_whitespace() || c == ',' || c == ';') {
+        if token.is_empty() {
+            continue;
+        }
+
+        let token = match token.strip_prefix("0x").or_else(|| token.strip_prefix("0X")) {
+            Some(stripped) => stripped.strip_suffix("u8").unwrap_or(stripped),
+            None => token,
+        };
+
+        if token.is_empty() || !token.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        if token.len() == 1 {
+            result.push(u8::from_str_radix(token, 16).ok()?);
+        } else if token.len() % 2 == 0 {
+            // Contiguous hex, every two digits are one byte.
+            for pair in token.as_bytes().chunks(2) {
+                result.push(u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?);
+            }
+        } else {
+            return None;
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_separated_hex() {
+        assert_eq!(parse_hex("DE AD BE EF"), Some(vec![0xDE, 0xAD, 0xBE, 0xEF]));
+        assert_eq!(parse_hex("de,ad;be\n\tef"), Some(vec![0xDE, 0xAD, 0xBE, 0xEF]));
+        assert_eq!(parse_hex("1 2 A"), Some(vec![0x01, 0x02, 0x0A]));
+    }
+
+    #[test]
+    fn parse_contiguous_hex() {
+        assert_eq!(parse_hex("DEADBEEF"), Some(vec![0xDE, 0xAD, 0xBE, 0xEF]));
+        assert_eq!(parse_hex("  deadbeef  "), Some(vec![0xDE, 0xAD, 0xBE, 0xEF]));
+    }
+
+    #[test]
+    fn parse_prefixed_hex() {
+        assert_eq!(parse_hex("0xDE 0XAD"), Some(vec![0xDE, 0xAD]));
+        assert_eq!(parse_hex("0x5"), Some(vec![0x05]));
+        // Longer numbers are split into bytes in the order they're written.
+        assert_eq!(parse_hex("0xDEAD"), Some(vec![0xDE, 0xAD]));
+        assert_eq!(parse_hex("0xDEADBEEF"), Some(vec![0xDE, 0xAD, 0xBE, 0xEF]));
+    }
+
+    #[test]
+    fn parse_arrays() {
+        assert_eq!(parse_hex("{ 0xDE, 0xAD }"), Some(vec![0xDE, 0xAD]));
+        assert_eq!(
+            parse_hex("unsigned char data[2] = { 0xDE, 0xAD };"),
+            Some(vec![0xDE, 0xAD])
+        );
+        assert_eq!(parse_hex("[0xDEu8, 0xAD]"), Some(vec![0xDE, 0xAD]));
+        assert_eq!(parse_hex("let data: [u8; 2] = [0xDE, 0xAD,];"), Some(vec![0xDE, 0xAD]));
+    }
+
+    #[test]
+    fn reject_invalid_hex() {
+        assert_eq!(parse_hex("de:ad"), None);
+        assert_eq!(parse_hex("DEA"), None);
+        assert_eq!(parse_hex("0xDEA"), None);
+        assert_eq!(parse_hex("0x"), None);
+        assert_eq!(parse_hex("hello"), None);
+        assert_eq!(parse_hex("0xDEu16"), None);
+    }
+
+    #[test]
+    fn parse_empty_text() {
+        assert_eq!(parse_hex(""), Some(vec![]));
+        assert_eq!(parse_hex("{}"), Some(vec![]));
+    }
+}
@@ -0,0 +1,259 @@
+//! A side-by-side comparison of two memory sources, see [`MemoryEditor::window_ui_comparison`].
+
+use std::ops::Range;
+
+use egui::{Context, Ui, Window};
+
+use crate::changes::ChangeTracker;
+use crate::history::EditHistory;
+use crate::memory_cache::{self, MemoryCache};
+use crate::memory_source::MemoryRegion;
+use crate::option_data::BetweenFrameData;
+use crate::utilities::AddressRangeExt;
+use crate::{clipboard, Address, MemoryEditor, MemorySource};
+
+/// The state of the right pane of a side-by-side comparison.
+///
+/// While the right pane is drawn its cache and change tracker are swapped with those of the editor, so that the panes
+/// can share all drawing code.
+#[derive(Debug, Clone)]
+pub(crate) struct Comparison {
+    /// Whether a comparison is drawn in this frame, values are only compared while it is.
+    pub is_shown: bool,
+    /// Whether the right pane is being drawn.
+    pub is_drawing_right: bool,
+    pub memory_cache: MemoryCache,
+    pub change_tracker: ChangeTracker,
+    /// The writes made by "Copy left to right", which are undone separately from the edits of the left source.
+    pub history: EditHistory,
+}
+
+impl Comparison {
+    pub fn new(undo_history_length: usize) -> Self {
+        Comparison {
+            is_shown: false,
+            is_drawing_right: false,
+            memory_cache: Default::default(),
+            change_tracker: Default::default(),
+            history: EditHistory::new(undo_history_length),
+        }
+    }
+}
+
+/// The right source is only ever written through "Copy left to right".
+struct ReadOnlySource<'a, S: ?Sized>(&'a mut S);
+
+impl<S: MemorySource + ?Sized> MemorySource for ReadOnlySource<'_, S> {
+    fn read(&mut self, address: Address) -> Option<u8> {
+        self.0.read(address)
+    }
+
+    fn peek(&mut self, address: Address) -> Option<u8> {
+        self.0.peek(address)
+    }
+
+    fn peek_range(&mut self, start: Address, buffer: &mut [Option<u8>]) {
+        self.0.peek_range(start, buffer)
+    }
+
+    fn is_writable(&self, _address: Address) -> bool {
+        false
+    }
+
+    fn is_peekable(&self, address: Address) -> bool {
+        self.0.is_peekable(address)
+    }
+
+    fn region(&self, address: Address) -> Option<MemoryRegion> {
+        self.0.region(address)
+    }
+}
+
+impl MemoryEditor {
+    /// Create a window and render two memory sources side by side, e.g. an emulator and a reference implementation.
+    ///
+    /// Both panes share the scroll position, the column layout and the selection, and values which differ between
+    /// the two sources are highlighted in both panes with [`crate::option_data::MemoryEditorOptions::diff_colour`].
+    ///
+    /// The `left` source is edited like in [`Self::window_ui_with_source`]. The `right` source is only written by
+    /// "Copy left to right", which copies the selected values of the left source to the right one.
+    ///
+    /// Sources backed by closures can be created with [`crate::memory_source::ClosureSource`].
+    ///
+    /// ```no_run
+    /// # use egui_memory_editor::MemoryEditor;
+    /// # use egui_memory_editor::memory_source::ClosureSource;
+    /// # let ctx = egui::Context::default();
+    /// let mut emulator = vec![0xFF; 0x100];
+    /// let mut reference = vec![0xFF; 0x100];
+    /// let mut is_open = true;
+    /// let mut memory_editor = MemoryEditor::new().with_address_range("Memory", 0..0x100);
+    ///
+    /// let read_fn = |mem: &mut Vec<u8>, addr: u64| mem.get(addr as usize).copied();
+    /// let mut reference_source = ClosureSource::read_only(&mut reference, read_fn);
+    /// memory_editor.window_ui_comparison(&ctx, &mut is_open, &mut emulator, &mut reference_source);
+    /// ```
+    pub fn window_ui_comparison<L: MemorySource + ?Sized, R: MemorySource + ?Sized>(
+        &mut self,
+        ctx: &Context,
+        is_open: &mut bool,
+        left: &mut L,
+        right: &mut R,
+    ) {
+        Window::new(self.window_name.clone())
+            .open(is_open)
+            .hscroll(false)
+            .vscroll(false)
+            .resizable(true)
+            .show(ctx, |ui| {
+                self.shrink_window_ui(ui);
+                self.draw_comparison_contents(ui, left, right);
+            });
+    }
+
+    /// Draws two memory sources side by side.
+    ///
+    /// Can be included in whatever container you want.
+    ///
+    /// Use [`Self::window_ui_comparison`] if you want to have a window with the contents instead.
+    pub fn draw_comparison_contents<L: MemorySource + ?Sized, R: MemorySource + ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        left: &mut L,
+        right: &mut R,
+    ) {
+        self.draw_contents(ui, left, Some(right));
+    }
+
+    /// Fetch the visible memory of the right source, and keep track of its changes.
+    pub(crate) fn fetch_comparison<R: MemorySource + ?Sized>(
+        &mut self,
+        ui: &Ui,
+        right: &mut R,
+        address_space: &Range<Address>,
+        row_length: usize,
+    ) {
+        let comparison = &mut self.comparison;
+        comparison.memory_cache.clear(address_space, row_length);
+        comparison.memory_cache.fetch(right, self.visible_range.clone());
+
+        if self.options.highlight_changes {
+            comparison.change_tracker.update(
+                &comparison.memory_cache,
+                address_space,
+                &self.visible_range,
+                row_length,
+                ui.input(|i| i.time),
+                self.options.change_highlight_duration,
+            );
+
+            if comparison.change_tracker.is_fading() {
+                ui.ctx().request_repaint();
+            }
+        } else {
+            comparison.change_tracker.clear();
+        }
+    }
+
+    /// Draw the buttons above the panes of the comparison.
+    ///
+    /// "Copy left to right" is only requested here, it's performed by [`Self::handle_copy_left_to_right`] once the
+    /// panes are drawn.
+    pub(crate) fn draw_comparison_toolbar<R: MemorySource + ?Sized>(&mut self, ui: &mut Ui, right: &mut R) {
+        let comparison = &mut self.comparison;
+        comparison.history.set_max_length(self.options.undo_history_length);
+
+        ui.horizontal(|ui| {
+            let selection = self.frame_data.selection.map(|selection| selection.range());
+
+            let response = ui
+                .add_enabled(selection.is_some(), egui::Button::new("Copy left to right"))
+                .on_hover_text("Copy the selected values of the left memory to the right memory");
+            if response.clicked() {
+                self.frame_data.pending_copy_left_to_right = selection;
+            }
+
+            let undo = ui.add_enabled(comparison.history.can_undo(), egui::Button::new("Undo copy"));
+            if undo.clicked() {
+                comparison.history.undo(right, &mut comparison.memory_cache);
+            }
+        });
+    }
+
+    /// Draw the right pane of one row, using the same layout as the left pane.
+    pub(crate) fn draw_comparison_row<R: MemorySource + ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        right: &mut R,
+        start_address: Address,
+        address_space: &Range<Address>,
+        show_ascii: bool,
+    ) {
+        ui.add(egui::Separator::default().vertical().spacing(3.0));
+
+        self.swap_comparison();
+        self.comparison.is_drawing_right = true;
+
+        let mut right = ReadOnlySource(right);
+        self.draw_memory_values(ui, &mut right, start_address, address_space);
+
+        if show_ascii {
+            self.draw_ascii_sidebar(ui, &mut right, start_address, address_space);
+        }
+
+        self.comparison.is_drawing_right = false;
+        self.swap_comparison();
+    }
+
+    /// The context menu of a value in the right pane.
+    pub(crate) fn draw_comparison_context_menu(
+        ui: &mut Ui,
+        frame_data: &mut BetweenFrameData,
+        memory_address: Address,
+    ) {
+        // Copy the selection, or just the address the menu was opened on if nothing is selected.
+        let copy_range = frame_data
+            .selection
+            .map_or(memory_address..memory_address + 1, |selection| selection.range());
+
+        if ui.button("Copy left to right").clicked() {
+            frame_data.pending_copy_left_to_right = Some(copy_range);
+            ui.close_menu();
+        }
+    }
+
+    /// Write any range requested by "Copy left to right" to the right source, as a single undoable operation.
+    pub(crate) fn handle_copy_left_to_right<L: MemorySource + ?Sized, R: MemorySource + ?Sized>(
+        &mut self,
+        left: &mut L,
+        right: &mut R,
+    ) {
+        let Some(range) = self.frame_data.pending_copy_left_to_right.take() else {
+            return;
+        };
+
+        if range.length() > clipboard::MAX_COPY_LENGTH as Address {
+            // Shown in the same window as the failures of other copies.
+            self.frame_data.copy_message = Some(format!(
+                "Copies are limited to {} MiB, select a smaller range",
+                clipboard::MAX_COPY_LENGTH / (1024 * 1024)
+            ));
+            return;
+        }
+
+        let values = memory_cache::peek_range(left, range.clone());
+        // Values which can't be peeked on the left aren't copied.
+        let writes = range.zip(values).filter_map(|(address, value)| Some((address, value?)));
+
+        let comparison = &mut self.comparison;
+        comparison
+            .history
+            .write(right, &mut comparison.memory_cache, "Copy left to right", writes);
+    }
+
+    /// Exchange the memory of the left and right panes.
+    fn swap_comparison(&mut self) {
+        std::mem::swap(&mut self.memory_cache, &mut self.comparison.memory_cache);
+        std::mem::swap(&mut self.change_tracker, &mut self.comparison.change_tracker);
+    }
+}
@@ -0,0 +1,385 @@
+//! Character encodings for the text sidebar.
+//!
+//! The sidebar decodes memory through a [`CharacterDecoder`], the built-in ones are listed in [`TextEncoding`]. Games
+//! with their own encoding can be read with a [`CharacterTable`].
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[cfg(feature = "shift-jis")]
+use encoding_rs::SHIFT_JIS;
+
+use crate::option_data::{MemoryEditorOptions, TextEncoding};
+
+/// Turns memory into the characters shown in the text sidebar, and typed characters back into memory.
+pub trait CharacterDecoder {
+    /// Decode the character at the start of `bytes`, returning it with the amount of bytes it takes up.
+    ///
+    /// `bytes` holds [`CharacterDecoder::max_length`] bytes, unless the end of the readable memory is reached.
+    /// Returns `None` if the bytes aren't a valid character, which is displayed as `.`.
+    fn decode(&self, bytes: &[u8]) -> Option<(String, usize)>;
+
+    /// Encode a typed `character`, returns `None` if it can't be represented.
+    fn encode(&self, character: char) -> Option<Vec<u8>>;
+
+    /// Encode all of `text`, e.g. for a search. Returns the first character which can't be represented on failure.
+    fn encode_str(&self, text: &str) -> Result<Vec<u8>, char> {
+        let mut bytes = Vec::with_capacity(text.len());
+
+        for character in text.chars() {
+            bytes.extend(self.encode(character).ok_or(character)?);
+        }
+
+        Ok(bytes)
+    }
+
+    /// The maximum amount of bytes a single character takes up.
+    fn max_length(&self) -> usize {
+        1
+    }
+
+    /// Characters only start at multiples of this amount of bytes, counting from the start of the address range.
+    fn alignment(&self) -> usize {
+        1
+    }
+}
+
+impl CharacterDecoder for TextEncoding {
+    fn decode(&self, bytes: &[u8]) -> Option<(String, usize)> {
+        let first = *bytes.first()?;
+
+        let (character, length) = match self {
+            TextEncoding::Ascii => (first.is_ascii().then_some(first as char)?, 1),
+            TextEncoding::Latin1 => (first as char, 1),
+            TextEncoding::Utf8 => {
+                let length = match first {
+                    0x00..=0x7F => 1,
+                    0xC2..=0xDF => 2,
+                    0xE0..=0xEF => 3,
+                    0xF0..=0xF4 => 4,
+                    _ => return None,
+                };
+                let text = std::str::from_utf8(bytes.get(..length)?).ok()?;
+                (text.chars().next()?, length)
+            }
+            TextEncoding::Utf16Le | TextEncoding::Utf16Be => {
+                let units = bytes
+                    .chunks_exact(2)
+                    .take(2)
+                    .map(|unit| match self {
+                        TextEncoding::Utf16Le => u16::from_le_bytes([unit[0], unit[1]]),
+                        _ => u16::from_be_bytes([unit[0], unit[1]]),
+                    })
+                    .collect::<Vec<_>>();
+                let character = char::decode_utf16(units).next()?.ok()?;
+                (character, character.len_utf16() * 2)
+            }
+            TextEncoding::Ebcdic => (first.checked_sub(0x40).map(|i| EBCDIC_037[i as usize])?, 1),
+            #[cfg(feature = "shift-jis")]
+            TextEncoding::ShiftJis => {
+                let length = match first {
+                    0x81..=0x9F | 0xE0..=0xFC => 2,
+                    _ => 1,
+                };
+                let text = SHIFT_JIS.decode_without_bom_handling_and_without_replacement(bytes.get(..length)?)?;
+                (text.chars().next()?, length)
+            }
+        };
+
+        // Control characters would mess up the layout of the sidebar.
+        (!character.is_control()).then(|| (character.to_string(), length))
+    }
+
+    fn encode(&self, character: char) -> Option<Vec<u8>> {
+        let code_point = character as u32;
+
+        match self {
+            TextEncoding::Ascii => character.is_ascii().then(|| vec![code_point as u8]),
+            TextEncoding::Latin1 => (code_point < 0x100).then(|| vec![code_point as u8]),
+            TextEncoding::Utf8 => Some(character.to_string().into_bytes()),
+            TextEncoding::Utf16Le => Some(
+                character
+                    .encode_utf16(&mut [0; 2])
+                    .iter()
+                    .flat_map(|u| u.to_le_bytes())
+                    .collect(),
+            ),
+            TextEncoding::Utf16Be => Some(
+                character
+                    .encode_utf16(&mut [0; 2])
+                    .iter()
+                    .flat_map(|u| u.to_be_bytes())
+                    .collect(),
+            ),
+            TextEncoding::Ebcdic => EBCDIC_037
+                .iter()
+                .position(|&c| c == character)
+                .map(|i| vec![i as u8 + 0x40]),
+            #[cfg(feature = "shift-jis")]
+            TextEncoding::ShiftJis => {
+                let mut buffer = [0; 4];
+                let (bytes, _, had_errors) = SHIFT_JIS.encode(character.encode_utf8(&mut buffer));
+                (!had_errors).then(|| bytes.into_owned())
+            }
+        }
+    }
+
+    fn max_length(&self) -> usize {
+        match self {
+            TextEncoding::Utf8 | TextEncoding::Utf16Le | TextEncoding::Utf16Be => 4,
+            #[cfg(feature = "shift-jis")]
+            TextEncoding::ShiftJis => 2,
+            _ => 1,
+        }
+    }
+
+    fn alignment(&self) -> usize {
+        match self {
+            TextEncoding::Utf16Le | TextEncoding::Utf16Be => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// The character table of the selected address range, if it has one.
+pub(crate) fn selected_table<'a>(
+    options: &MemoryEditorOptions,
+    character_tables: &'a BTreeMap<String, CharacterTable>,
+) -> Option<&'a CharacterTable> {
+    let table_name = options.range_character_tables.get(&options.selected_address_range)?;
+    character_tables.get(table_name)
+}
+
+/// The decoder of the text sidebar, the character table of the selected address range or the selected encoding.
+pub(crate) fn selected_decoder<'a>(
+    options: &'a MemoryEditorOptions,
+    character_tables: &'a BTreeMap<String, CharacterTable>,
+) -> &'a dyn CharacterDecoder {
+    match selected_table(options, character_tables) {
+        Some(table) => table,
+        None => &options.text_encoding,
+    }
+}
+
+/// A custom character encoding, which maps sequences of bytes to text, as used by ROM hacking tools.
+///
+/// ```
+/// # use egui_memory_editor::encoding::{CharacterDecoder, CharacterTable};
+/// let table = CharacterTable::from_tbl("00=A\n01=B\n8000=the \n*FF").unwrap();
+///
+/// assert_eq!(table.decode(&[0x80, 0x00]), Some(("the ".to_string(), 2)));
+/// assert_eq!(table.encode_str("the BA"), Ok(vec![0x80, 0x00, 0x01, 0x00]));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CharacterTable {
+    entries: BTreeMap<Vec<u8>, String>,
+    /// The length of the longest sequence of bytes in the table.
+    max_length: usize,
+}
+
+impl CharacterTable {
+    /// Parse the contents of a Thingy style `.tbl` file.
+    ///
+    /// Every line maps hex bytes to text, e.g. `8140=あ`. `*XX` marks a line break, and `/XX=text` an end token
+    /// (shown as `■` without a text).
+    /// Table switching (`!XX`), control codes (`$XX`) and table ids (`@name`) aren't supported, and are skipped.
+    pub fn from_tbl(text: &str) -> Result<Self, String> {
+        let mut table = CharacterTable::default();
+        let text = text.strip_prefix('\u{FEFF}').unwrap_or(text);
+
+        for (number, line) in text.lines().enumerate() {
+            let invalid = || format!("Line {}: `{}` isn't a valid entry", number + 1, line);
+
+            if line.trim().is_empty() || line.starts_with(['!', '$', '@']) {
+                continue;
+            }
+
+            // The text isn't trimmed, as a space is a valid character.
+            let (hex, text) = match line.split_once('=') {
+                Some((hex, text)) => (hex, text.to_string()),
+                None => (line, String::new()),
+            };
+            let (hex, text) = if let Some(hex) = hex.strip_prefix('*') {
+                (hex, "\n".to_string())
+            } else if let Some(hex) = hex.strip_prefix('/') {
+                let text = if text.is_empty() { "■".to_string() } else { text };
+                (hex, text)
+            } else if !text.is_empty() {
+                (hex, text)
+            } else {
+                return Err(invalid());
+            };
+
+            let hex = hex.trim();
+            if hex.is_empty() || hex.len() % 2 != 0 {
+                return Err(invalid());
+            }
+            let bytes = (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+                .collect::<Option<Vec<u8>>>()
+                .ok_or_else(invalid)?;
+
+            table.insert(bytes, text);
+        }
+
+        Ok(table)
+    }
+
+    /// Read and parse a Thingy style `.tbl` file, see [`Self::from_tbl`].
+    ///
+    /// The file is expected to be UTF-8, invalid characters are replaced.
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let contents = std::fs::read(path)?;
+
+        Self::from_tbl(&String::from_utf8_lossy(&contents))
+            .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidData, message))
+    }
+
+    /// Add, or replace, the text of a sequence of bytes.
+    pub fn insert(&mut self, bytes: Vec<u8>, text: impl Into<String>) {
+        self.max_length = self.max_length.max(bytes.len());
+        self.entries.insert(bytes, text.into());
+    }
+
+    /// The amount of entries in the table.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The bytes of the entry with the longest text at the start of `text`, and the length of that text in bytes.
+    /// If several entries have the same text, the shortest sequence of bytes is used.
+    fn longest_prefix(&self, text: &str) -> Option<(&[u8], usize)> {
+        self.entries
+            .iter()
+            .filter(|(_, entry)| !entry.is_empty() && text.starts_with(entry.as_str()))
+            .min_by_key(|(bytes, entry)| (std::cmp::Reverse(entry.len()), bytes.len()))
+            .map(|(bytes, entry)| (bytes.as_slice(), entry.len()))
+    }
+}
+
+impl CharacterDecoder for CharacterTable {
+    fn decode(&self, bytes: &[u8]) -> Option<(String, usize)> {
+        // The longest sequence wins, so that multi-byte entries take priority over their first byte.
+        (1..=self.max_length.min(bytes.len())).rev().find_map(|length| {
+            let text = self.entries.get(&bytes[..length])?;
+            // Line breaks would mess up the layout of the sidebar.
+            Some((text.replace('\n', "↵"), length))
+        })
+    }
+
+    fn encode(&self, character: char) -> Option<Vec<u8>> {
+        let text = character.encode_utf8(&mut [0; 4]).to_string();
+
+        self.longest_prefix(&text)
+            .filter(|(_, length)| *length == text.len())
+            .map(|(bytes, _)| bytes.to_vec())
+    }
+
+    /// Greedily encodes the longest entry at every position, so that e.g. `the` is written as one entry if the table
+    /// has one.
+    fn encode_str(&self, mut text: &str) -> Result<Vec<u8>, char> {
+        let mut bytes = Vec::with_capacity(text.len());
+
+        while let Some(character) = text.chars().next() {
+            let (entry, length) = self.longest_prefix(text).ok_or(character)?;
+            bytes.extend_from_slice(entry);
+            text = &text[length..];
+        }
+
+        Ok(bytes)
+    }
+
+    fn max_length(&self) -> usize {
+        self.max_length.max(1)
+    }
+}
+
+/// The characters of EBCDIC code page 037 from `0x40` onwards, everything below is a control character.
+#[rustfmt::skip]
+const EBCDIC_037: [char; 0xC0] = [
+    ' ', '\u{A0}', 'â', 'ä', 'à', 'á', 'ã', 'å', 'ç', 'ñ', '¢', '.', '<', '(', '+', '|',
+    '&', 'é', 'ê', 'ë', 'è', 'í', 'î', 'ï', 'ì', 'ß', '!', '$', '*', ')', ';', '¬',
+    '-', '/', 'Â', 'Ä', 'À', 'Á', 'Ã', 'Å', 'Ç', 'Ñ', '¦', ',', '%', '_', '>', '?',
+    'ø', 'É', 'Ê', 'Ë', 'È', 'Í', 'Î', 'Ï', 'Ì', '`', ':', '#', '@', '\'', '=', '"',
+    'Ø', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', '«', '»', 'ð', 'ý', 'þ', '±',
+    '°', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 'ª', 'º', 'æ', '¸', 'Æ', '¤',
+    'µ', '~', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '¡', '¿', 'Ð', 'Ý', 'Þ', '®',
+    '^', '£', '¥', '·', '©', '§', '¶', '¼', '½', '¾', '[', ']', '¯', '¨', '´', '×',
+    '{', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', '\u{AD}', 'ô', 'ö', 'ò', 'ó', 'õ',
+    '}', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', '¹', 'û', 'ü', 'ù', 'ú', 'ÿ',
+    '\\', '÷', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '²', 'Ô', 'Ö', 'Ò', 'Ó', 'Õ',
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '³', 'Û', 'Ü', 'Ù', 'Ú', '\u{9F}',
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multi_byte_keys() {
+        let table = CharacterTable::from_tbl("8140=あ\n41=A\n80=x\n8000=the ").unwrap();
+
+        assert_eq!(table.len(), 4);
+        assert_eq!(table.decode(&[0x81, 0x40]), Some(("あ".to_string(), 2)));
+        assert_eq!(table.decode(&[0x41, 0x81]), Some(("A".to_string(), 1)));
+        // Only the first byte of a multi-byte key isn't a character.
+        assert_eq!(table.decode(&[0x81, 0x41]), None);
+        // The longest key wins.
+        assert_eq!(table.decode(&[0x80, 0x00]), Some(("the ".to_string(), 2)));
+        assert_eq!(table.decode(&[0x80, 0x01]), Some(("x".to_string(), 1)));
+        assert_eq!(table.decode(&[0x80]), Some(("x".to_string(), 1)));
+    }
+
+    #[test]
+    fn control_entries() {
+        let table = CharacterTable::from_tbl("\u{FEFF}*FF\n/00=<end>\n/01\n20= \n\n!80=1\n$81=Pause\n@main\n").unwrap();
+
+        assert_eq!(table.len(), 4);
+        // Line breaks are shown as a symbol, but still encoded.
+        assert_eq!(table.decode(&[0xFF]), Some(("↵".to_string(), 1)));
+        assert_eq!(table.encode_str("\n"), Ok(vec![0xFF]));
+        assert_eq!(table.decode(&[0x00]), Some(("<end>".to_string(), 1)));
+        assert_eq!(table.decode(&[0x01]), Some(("■".to_string(), 1)));
+        // The text isn't trimmed, so spaces can be mapped.
+        assert_eq!(table.decode(&[0x20]), Some((" ".to_string(), 1)));
+        // Table switches, control codes and table ids are skipped.
+        assert_eq!(table.decode(&[0x80]), None);
+        assert_eq!(table.decode(&[0x81]), None);
+    }
+
+    #[test]
+    fn malformed_lines() {
+        assert_eq!(
+            CharacterTable::from_tbl("41=A\n4=B"),
+            Err("Line 2: `4=B` isn't a valid entry".to_string())
+        );
+        assert!(CharacterTable::from_tbl("ZZ=A").is_err());
+        assert!(CharacterTable::from_tbl("414=A").is_err());
+        assert!(CharacterTable::from_tbl("=A").is_err());
+        assert!(CharacterTable::from_tbl("41").is_err());
+        assert!(CharacterTable::from_tbl("41=").is_err());
+        assert!(CharacterTable::from_tbl("*").is_err());
+        assert!(CharacterTable::from_tbl("é1=A").is_err());
+    }
+
+    #[test]
+    fn greedy_encoding() {
+        let table = CharacterTable::from_tbl("00=t\n01=h\n02=e\n03=the\n04= \n10=A\n2000=A").unwrap();
+
+        // The longest text is used, falling back to shorter entries.
+        assert_eq!(table.encode_str("the the"), Ok(vec![0x03, 0x04, 0x03]));
+        assert_eq!(table.encode_str("th e"), Ok(vec![0x00, 0x01, 0x04, 0x02]));
+        // Of entries with the same text, the shortest sequence of bytes is used.
+        assert_eq!(table.encode_str("A"), Ok(vec![0x10]));
+        assert_eq!(table.encode('A'), Some(vec![0x10]));
+        // Single characters are only encoded if an entry is exactly that character.
+        assert_eq!(table.encode('t'), Some(vec![0x00]));
+        assert_eq!(table.encode('x'), None);
+        assert_eq!(table.encode_str("tax"), Err('a'));
+    }
+}
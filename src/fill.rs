@@ -0,0 +1,328 @@
+use std::ops::Range;
+
+use egui::{Align2, Context, RichText, Ui};
+
+use crate::option_data::{DataFormatType, DataPreviewOptions, Endianness};
+use crate::option_ui::data_format_combo;
+use crate::utilities::AddressRangeExt;
+use crate::{clipboard, utilities, Address, MemoryEditor, MemorySource};
+
+/// The maximum amount of bytes to show in the preview of the fill dialog.
+const PREVIEW_LENGTH: usize = 32;
+/// Fills are generated in memory before they're written, so they're limited to this many bytes.
+const MAX_FILL_LENGTH: usize = 64 * 1024 * 1024;
+
+/// How the fill dialog generates the bytes to write.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub(crate) enum FillKind {
+    /// A single byte, e.g. `00` or `CC`.
+    #[default]
+    Constant,
+    /// A repeating sequence of hex bytes.
+    Pattern,
+    /// Values of a number type which go up, or down, by a step.
+    Increment,
+    Decrement,
+    /// Pseudo random bytes, the same seed always gives the same bytes.
+    Random,
+}
+
+impl FillKind {
+    pub fn iter() -> impl Iterator<Item = FillKind> {
+        use FillKind::*;
+        [Constant, Pattern, Increment, Decrement, Random].into_iter()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FillKind::Constant => "Constant byte",
+            FillKind::Pattern => "Repeating pattern",
+            FillKind::Increment => "Incrementing values",
+            FillKind::Decrement => "Decrementing values",
+            FillKind::Random => "Random bytes",
+        }
+    }
+}
+
+/// The state of the "Fill selection" dialog, the settings are kept between uses.
+#[derive(Debug, Clone)]
+pub(crate) struct FillDialog {
+    /// The range to fill, the dialog is only shown while this is set.
+    pub range: Option<Range<Address>>,
+    pub kind: FillKind,
+    pub constant: String,
+    pub pattern: String,
+    /// The number type of the incrementing or decrementing values.
+    pub format: DataPreviewOptions,
+    pub start: String,
+    pub step: String,
+    pub seed: String,
+}
+
+impl Default for FillDialog {
+    fn default() -> Self {
+        FillDialog {
+            range: None,
+            kind: FillKind::Constant,
+            constant: "00".to_string(),
+            pattern: "DE AD BE EF".to_string(),
+            format: DataPreviewOptions {
+                selected_endianness: Endianness::Little,
+                selected_data_format: DataFormatType::U8,
+            },
+            start: "0".to_string(),
+            step: "1".to_string(),
+            seed: "0".to_string(),
+        }
+    }
+}
+
+impl FillDialog {
+    /// Generate the first `length` bytes of the fill, or a message for the user if the settings aren't valid.
+    ///
+    /// Values which don't fit entirely at the end are truncated.
+    pub fn generate(&self, length: usize) -> Result<Vec<u8>, String> {
+        let bytes = match self.kind {
+            FillKind::Constant => match clipboard::parse_hex(&self.constant).as_deref() {
+                Some(&[value]) => vec![value; length],
+                _ => return Err("The constant has to be a single hex byte, e.g. CC".to_string()),
+            },
+            FillKind::Pattern => match clipboard::parse_hex(&self.pattern) {
+                Some(pattern) if !pattern.is_empty() => pattern.into_iter().cycle().take(length).collect(),
+                _ => return Err("The pattern has to consist of hex bytes, e.g. DE AD BE EF".to_string()),
+            },
+            FillKind::Increment | FillKind::Decrement => self.sequence(length)?,
+            FillKind::Random => {
+                let seed = self
+                    .seed
+                    .trim()
+                    .parse::<u64>()
+                    .map_err(|_| "The seed has to be a whole number".to_string())?;
+                let mut rng = SplitMix64(seed);
+
+                (0..length.div_ceil(8))
+                    .flat_map(|_| rng.next().to_le_bytes())
+                    .take(length)
+                    .collect()
+            }
+        };
+
+        Ok(bytes)
+    }
+
+    /// Consecutive values of the number type, starting at `start` and changing by `step`.
+    ///
+    /// Binary integers wrap around on overflow, floats and fixed point values are accumulated as `f64` and rounded to
+    /// the number type. Other values, like BCD, have to stay within the range of the number type.
+    fn sequence(&self, length: usize) -> Result<Vec<u8>, String> {
+        use DataFormatType::*;
+
+        let format = self.format.selected_data_format;
+        let width = format.bytes_to_read();
+        let mut bytes = Vec::with_capacity(length.saturating_add(width));
+
+        // Validates that the start fits in the number type.
+        utilities::decimal_string_to_bytes(self.format, &self.start)?;
+
+        match format {
+            F16 | BF16 | F32 | F64 | Q16 { .. } | Q32 { .. } => {
+                let start: f64 = self.start.trim().parse().map_err(|e| format!("{}", e))?;
+                let step: f64 = self.step.trim().parse().map_err(|e| format!("{}", e))?;
+                let step = if self.kind == FillKind::Decrement { -step } else { step };
+
+                for i in 0.. {
+                    if bytes.len() >= length {
+                        break;
+                    }
+                    let value = start + step * i as f64;
+                    bytes.extend(utilities::decimal_string_to_bytes(self.format, &value.to_string())?);
+                }
+            }
+            _ => {
+                let start = parse_integer(&self.start)?;
+                let step = parse_integer(&self.step)?;
+                let step = if self.kind == FillKind::Decrement {
+                    step.wrapping_neg()
+                } else {
+                    step
+                };
+                let wraps = matches!(
+                    format,
+                    U8 | U16 | U24 | U32 | U64 | U128 | I8 | I16 | I24 | I32 | I64 | I128
+                );
+
+                for i in 0.. {
+                    if bytes.len() >= length {
+                        break;
+                    }
+                    let value = start.wrapping_add(step.wrapping_mul(i as i128));
+
+                    if wraps {
+                        // Truncating the two's complement representation wraps the value around.
+                        let mut value_bytes = value.to_le_bytes()[..width].to_vec();
+                        if self.format.selected_endianness == Endianness::Big {
+                            value_bytes.reverse();
+                        }
+                        bytes.extend(value_bytes);
+                    } else {
+                        bytes.extend(utilities::decimal_string_to_bytes(self.format, &value.to_string())?);
+                    }
+                }
+            }
+        }
+
+        bytes.truncate(length);
+        Ok(bytes)
+    }
+}
+
+fn parse_integer(text: &str) -> Result<i128, String> {
+    text.trim().parse::<i128>().map_err(|e| e.to_string())
+}
+
+/// A small, seedable pseudo random number generator, good enough for filling memory.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl MemoryEditor {
+    /// Draw the "Fill selection" dialog, if it was opened through the context menu.
+    pub(crate) fn draw_fill_dialog<S: MemorySource + ?Sized>(&mut self, ctx: &Context, source: &mut S) {
+        let Some(range) = self.frame_data.fill.range.clone() else {
+            return;
+        };
+
+        let dialog = &mut self.frame_data.fill;
+        let text_style = self.options.memory_editor_text_style.clone();
+        let mut confirmed = None;
+
+        egui::Window::new("Fill selection")
+            .id(egui::Id::new(&self.window_name).with("fill_dialog"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Fill {} bytes from {:#X} to {:#X}",
+                    range.length(),
+                    range.start,
+                    range.end.saturating_sub(1)
+                ));
+
+                egui::ComboBox::from_id_salt("FillKindCombo")
+                    .selected_text(dialog.kind.name())
+                    .show_ui(ui, |ui| {
+                        for kind in FillKind::iter() {
+                            ui.selectable_value(&mut dialog.kind, kind, kind.name());
+                        }
+                    });
+
+                Self::draw_fill_settings(ui, dialog);
+
+                let preview = if range.length() > MAX_FILL_LENGTH as Address {
+                    Err(format!(
+                        "Fills are limited to {} MiB, select a smaller range",
+                        MAX_FILL_LENGTH / (1024 * 1024)
+                    ))
+                } else {
+                    dialog.generate(range.length().min(PREVIEW_LENGTH as Address) as usize)
+                };
+
+                match &preview {
+                    Ok(bytes) => {
+                        let mut preview = bytes
+                            .iter()
+                            .map(|value| format!("{:02X}", value))
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        if range.length() > PREVIEW_LENGTH as Address {
+                            preview.push_str(" ...");
+                        }
+                        ui.label(RichText::new(preview).text_style(text_style));
+                    }
+                    Err(message) => {
+                        ui.colored_label(ui.visuals().error_fg_color, message);
+                    }
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(preview.is_ok(), egui::Button::new("Fill")).clicked() {
+                        confirmed = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        confirmed = Some(false);
+                    }
+                });
+            });
+
+        match confirmed {
+            Some(true) if range.length() <= MAX_FILL_LENGTH as Address => {
+                if let Ok(bytes) = self.frame_data.fill.generate(range.length() as usize) {
+                    let writes = range.clone().zip(bytes);
+                    self.history.write(source, &mut self.memory_cache, "Fill", writes);
+                }
+                self.frame_data.fill.range = None;
+            }
+            Some(_) => self.frame_data.fill.range = None,
+            None => {}
+        }
+    }
+
+    /// The settings of the selected [`FillKind`].
+    fn draw_fill_settings(ui: &mut Ui, dialog: &mut FillDialog) {
+        match dialog.kind {
+            FillKind::Constant => {
+                ui.horizontal(|ui| {
+                    ui.label("Byte:");
+                    ui.add(egui::TextEdit::singleline(&mut dialog.constant).desired_width(30.0));
+                });
+            }
+            FillKind::Pattern => {
+                ui.horizontal(|ui| {
+                    ui.label("Pattern:");
+                    ui.text_edit_singleline(&mut dialog.pattern);
+                });
+            }
+            FillKind::Increment | FillKind::Decrement => {
+                ui.horizontal(|ui| {
+                    let format = &mut dialog.format;
+
+                    egui::ComboBox::from_id_salt("FillEndiannessCombo")
+                        .selected_text(format!("{:?}", format.selected_endianness))
+                        .show_ui(ui, |ui| {
+                            for endian in Endianness::iter() {
+                                ui.selectable_value(&mut format.selected_endianness, endian, format!("{:?}", endian));
+                            }
+                        });
+
+                    data_format_combo(
+                        ui,
+                        egui::ComboBox::from_id_salt("FillFormatCombo"),
+                        &mut format.selected_data_format,
+                    );
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Start:");
+                    ui.add(egui::TextEdit::singleline(&mut dialog.start).desired_width(100.0));
+                    ui.label("Step:");
+                    ui.add(egui::TextEdit::singleline(&mut dialog.step).desired_width(60.0));
+                });
+            }
+            FillKind::Random => {
+                ui.horizontal(|ui| {
+                    ui.label("Seed:");
+                    ui.add(egui::TextEdit::singleline(&mut dialog.seed).desired_width(100.0));
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! The undo/redo history of all writes made through the [`crate::MemoryEditor`].
+use std::collections::VecDeque;
+
+use crate::memory_cache::MemoryCache;
+use crate::{Address, MemorySource};
+
+/// Groups with more writes than this aren't kept, as every write is stored with its previous value.
+pub(crate) const MAX_GROUP_LENGTH: usize = 1024 * 1024;
+
+/// A single byte written by the editor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MemoryWrite {
+    pub address: Address,
+    /// The value before the write.
+    pub old_value: u8,
+    pub new_value: u8,
+}
+
+/// A group of writes which are undone and redone as one, e.g. a single paste.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditGroup {
+    /// A short description of the operation, e.g. `Paste`.
+    pub description: String,
+    /// The writes of the group, empty if it can't be undone.
+    pub writes: Vec<MemoryWrite>,
+    /// Why the group can't be undone, if it can't.
+    ///
+    /// Groups are not undoable if they wrote memory which can't be peeked, as its previous values are unknown, or if
+    /// they were too large to be kept. Nothing before such a group can be undone either.
+    pub irreversible_reason: Option<String>,
+}
+
+impl EditGroup {
+    pub fn is_undoable(&self) -> bool {
+        self.irreversible_reason.is_none()
+    }
+}
+
+/// The undo and redo stacks of the editor.
+///
+/// Can be accessed through [`crate::MemoryEditor::history`].
+#[derive(Debug, Clone)]
+pub struct EditHistory {
+    undo: VecDeque<EditGroup>,
+    redo: Vec<EditGroup>,
+    max_length: usize,
+}
+
+impl EditHistory {
+    pub(crate) fn new(max_length: usize) -> Self {
+        EditHistory {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            max_length,
+        }
+    }
+
+    /// The groups which can be undone, from oldest to most recent.
+    pub fn undo_entries(&self) -> impl DoubleEndedIterator<Item = &EditGroup> {
+        self.undo.iter()
+    }
+
+    /// The groups which can be redone, from the next one to redo to the last.
+    pub fn redo_entries(&self) -> impl DoubleEndedIterator<Item = &EditGroup> {
+        self.redo.iter().rev()
+    }
+
+    /// Whether there is a group to undo, and it is undoable.
+    pub fn can_undo(&self) -> bool {
+        self.undo.back().is_some_and(EditGroup::is_undoable)
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Remove all entries from both the undo and redo stacks.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Set the maximum amount of groups kept in the undo stack, dropping the oldest ones if there are more.
+    pub(crate) fn set_max_length(&mut self, max_length: usize) {
+        self.max_length = max_length;
+        while self.undo.len() > self.max_length {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Write all `writes` through `source` as one undoable group, skipping addresses which aren't writable.
+    ///
+    /// The previous values are peeked before writing. If that isn't possible, or the group has more than
+    /// [`MAX_GROUP_LENGTH`] writes, the group is recorded as not undoable instead.
+    /// Returns the amount of bytes written.
+    pub(crate) fn write<S: MemorySource + ?Sized>(
+        &mut self,
+        source: &mut S,
+        memory_cache: &mut MemoryCache,
+        description: impl Into<String>,
+        writes: impl IntoIterator<Item = (Address, u8)>,
+    ) -> usize {
+        let mut recorded = Vec::new();
+        let mut irreversible_reason = None;
+        let mut written = 0;
+
+        for (address, new_value) in writes {
+            if !source.is_writable(address) {
+                continue;
+            }
+
+            if irreversible_reason.is_none() {
+                let old_value = if source.is_peekable(address) {
+                    source.peek(address)
+                } else {
+                    None
+                };
+
+                match old_value {
+                    Some(old_value) if recorded.len() < MAX_GROUP_LENGTH => recorded.push(MemoryWrite {
+                        address,
+                        old_value,
+                        new_value,
+                    }),
+                    Some(_) => {
+                        irreversible_reason = Some(format!("it wrote more than {} bytes", MAX_GROUP_LENGTH));
+                    }
+                    None => {
+                        irreversible_reason = Some(format!("the previous value at {:#X} couldn't be peeked", address));
+                    }
+                }
+
+                if irreversible_reason.is_some() {
+                    recorded = Vec::new();
+                }
+            }
+
+            source.write(address, new_value);
+            memory_cache.set(address, new_value);
+            written += 1;
+        }
+
+        if written > 0 && self.max_length > 0 {
+            self.redo.clear();
+            self.undo.push_back(EditGroup {
+                description: description.into(),
+                writes: recorded,
+                irreversible_reason,
+            });
+            self.set_max_length(self.max_length);
+        }
+
+        written
+    }
+
+    /// Undo the most recent group, returning `false` if there was nothing to undo or it isn't undoable.
+    pub(crate) fn undo<S: MemorySource + ?Sized>(&mut self, source: &mut S, memory_cache: &mut MemoryCache) -> bool {
+        if !self.can_undo() {
+            return false;
+        }
+        let Some(group) = self.undo.pop_back() else {
+            return false;
+        };
+
+        for write in group.writes.iter().rev() {
+            source.write(write.address, write.old_value);
+            memory_cache.set(write.address, write.old_value);
+        }
+
+        self.redo.push(group);
+        true
+    }
+
+    /// Redo the most recently undone group, returning `false` if there was nothing to redo.
+    ///
+    /// Addresses which are no longer writable are skipped.
+    pub(crate) fn redo<S: MemorySource + ?Sized>(&mut self, source: &mut S, memory_cache: &mut MemoryCache) -> bool {
+        let Some(group) = self.redo.pop() else {
+            return false;
+        };
+
+        for write in &group.writes {
+            if !source.is_writable(write.address) {
+                continue;
+            }
+
+            source.write(write.address, write.new_value);
+            memory_cache.set(write.address, write.new_value);
+        }
+
+        self.undo.push_back(group);
+        true
+    }
+}
@@ -0,0 +1,154 @@
+use std::ops::Range;
+
+use egui::Ui;
+
+use crate::option_data::{DataFormatType, DataPreviewOptions, Endianness};
+use crate::{utilities, Address, MemoryEditor, MemorySource};
+
+/// The maximum amount of bytes shown by the null-terminated string row.
+const MAX_STRING_LENGTH: usize = 64;
+
+impl MemoryEditor {
+    /// Draw a table with every interpretation of the memory at the cursor, clicking a value copies it.
+    ///
+    /// The cursor is the edit cursor, the start of the selection, or the highlighted address, in that order.
+    pub(crate) fn draw_data_inspector<S: MemorySource + ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        current_address_range: &Range<Address>,
+        source: &mut S,
+    ) {
+        egui::CollapsingHeader::new("🔬 Data Inspector")
+            .default_open(false)
+            .show(ui, |ui| {
+                let frame_data = &self.frame_data;
+                let cursor = frame_data
+                    .selected_edit_address
+                    .or(frame_data.selection.map(|selection| selection.start()))
+                    .or(frame_data.selected_highlight_address);
+
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_label("Pointer size")
+                        .selected_text(format!("{} bytes", self.options.inspector_pointer_size))
+                        .show_ui(ui, |ui| {
+                            for size in [2, 4, 8] {
+                                ui.selectable_value(
+                                    &mut self.options.inspector_pointer_size,
+                                    size,
+                                    format!("{} bytes", size),
+                                );
+                            }
+                        });
+                });
+
+                let Some(address) = cursor.filter(|address| current_address_range.contains(address)) else {
+                    ui.label("Click or select a value in the UI to inspect it");
+                    return;
+                };
+
+                let end = address
+                    .saturating_add(MAX_STRING_LENGTH as Address)
+                    .min(current_address_range.end);
+                self.memory_cache.fetch(source, address..end);
+                let bytes: Vec<Option<u8>> = (address..end).map(|a| self.memory_cache.get(a)).collect();
+                let rows = inspector_rows(&bytes, self.options.inspector_pointer_size);
+                let none_value = &self.options.none_display_value;
+
+                ui.label(format!("Values at {:#X}, click a value to copy it", address));
+
+                egui::Grid::new("data_inspector_grid").striped(true).show(ui, |ui| {
+                    ui.strong("Type");
+                    ui.strong("Little endian");
+                    ui.strong("Big endian");
+                    ui.end_row();
+
+                    for (name, values) in rows {
+                        ui.label(name);
+
+                        for value in values.into_iter().flatten() {
+                            let text = value.as_deref().unwrap_or(none_value);
+                            let response = ui.add(egui::Label::new(text).selectable(false).sense(egui::Sense::click()));
+
+                            if let Some(value) = value.filter(|_| response.clicked()) {
+                                ui.ctx().copy_text(value);
+                            }
+                            response.on_hover_text("Click to copy");
+                        }
+
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+}
+
+/// The little and big endian values of every row of the data inspector, `None` where the memory couldn't be read.
+/// Rows which don't depend on endianness only have one value.
+fn inspector_rows(bytes: &[Option<u8>], pointer_size: usize) -> Vec<(String, [Option<Option<String>>; 2])> {
+    let mut rows = Vec::new();
+    // The first `length` bytes, if all of them could be read.
+    let take = |length: usize| -> Option<Vec<u8>> { bytes.get(..length)?.iter().copied().collect() };
+    let first = take(1).map(|bytes| bytes[0]);
+
+    for format in DataFormatType::iter() {
+        let width = format.bytes_to_read();
+        let value = |endianness| {
+            let data_preview = DataPreviewOptions {
+                selected_endianness: endianness,
+                selected_data_format: format,
+            };
+            take(width).map(|bytes| utilities::slice_to_decimal_string(data_preview, &bytes))
+        };
+        let big_endian = format.has_endianness().then(|| value(Endianness::Big));
+
+        rows.push((format.name().to_string(), [Some(value(Endianness::Little)), big_endian]));
+    }
+
+    rows.push(("Binary".to_string(), [Some(first.map(|v| format!("{:08b}", v))), None]));
+    rows.push(("Octal".to_string(), [Some(first.map(|v| format!("{:03o}", v))), None]));
+    rows.push(("Hex".to_string(), [Some(first.map(|v| format!("{:02X}", v))), None]));
+
+    let pointer = |endianness| {
+        take(pointer_size).map(|mut bytes| {
+            if endianness == Endianness::Big {
+                bytes.reverse();
+            }
+            let digits: String = bytes.iter().rev().map(|value| format!("{:02X}", value)).collect();
+            format!("0x{}", digits)
+        })
+    };
+    rows.push((
+        format!("Pointer ({} bytes)", pointer_size),
+        [Some(pointer(Endianness::Little)), Some(pointer(Endianness::Big))],
+    ));
+
+    let utf16 = |endianness| {
+        let units: Vec<u16> = take(4)
+            .or_else(|| take(2))?
+            .chunks_exact(2)
+            .map(|unit| match endianness {
+                Endianness::Little => u16::from_le_bytes([unit[0], unit[1]]),
+                Endianness::Big => u16::from_be_bytes([unit[0], unit[1]]),
+            })
+            .collect();
+
+        Some(match char::decode_utf16(units).next()? {
+            Ok(c) => format!("{:?} U+{:04X}", c, c as u32),
+            Err(_) => "Invalid".to_string(),
+        })
+    };
+    rows.push((
+        "UTF-16 char".to_string(),
+        [Some(utf16(Endianness::Little)), Some(utf16(Endianness::Big))],
+    ));
+
+    // Stop at the terminator, or at the first value which couldn't be read.
+    let string: Vec<u8> = bytes.iter().map_while(|value| value.filter(|&v| v != 0)).collect();
+    let mut string = format!("{:?}", String::from_utf8_lossy(&string));
+    if bytes.len() == MAX_STRING_LENGTH && !bytes.contains(&Some(0)) {
+        string.push('…');
+    }
+    rows.push(("String".to_string(), [Some(first.map(|_| string)), None]));
+
+    rows
+}
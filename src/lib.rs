@@ -7,16 +7,42 @@
 use std::collections::BTreeMap;
 use std::ops::Range;
 
-use egui::{Context, Label, Margin, RichText, ScrollArea, Sense, TextEdit, TextWrapMode, Ui, Vec2, Widget, Window};
-
-use crate::option_data::{BetweenFrameData, MemoryEditorOptions};
-
+use egui::{Context, Label, Margin, RichText, Sense, TextEdit, TextWrapMode, Ui, Vec2, Widget, Window};
+
+use crate::changes::ChangeTracker;
+use crate::comparison::Comparison;
+use crate::encoding::CharacterTable;
+use crate::history::EditHistory;
+use crate::memory_cache::MemoryCache;
+pub use crate::memory_source::MemorySource;
+use crate::memory_source::{ClosureSource, SharedMemorySource};
+use crate::option_data::{BetweenFrameData, CopyFormat, MemoryEditorOptions};
+use crate::selection::{Pane, Selection};
+use crate::snapshot::MemorySnapshot;
+use crate::utilities::AddressRangeExt;
+
+mod changes;
+mod clipboard;
+mod comparison;
+pub mod encoding;
+mod fill;
+pub mod history;
+mod inspector;
+mod memory_cache;
+pub mod memory_source;
 pub mod option_data;
 mod option_ui;
+mod paste;
+mod scanner;
+mod search;
+mod selection;
+pub mod snapshot;
 mod utilities;
 
 /// A memory address that should be read from/written to.
-pub type Address = usize;
+///
+/// This is a `u64` on every host, so the full 64-bit address space can be inspected from 32-bit hosts as well.
+pub type Address = u64;
 
 /// The main struct for the editor window.
 /// This should persist between frames as it keeps track of quite a bit of state.
@@ -26,7 +52,7 @@ pub struct MemoryEditor {
     window_name: String,
     /// The collection of address ranges, the GUI will start at the lower bound and go up to the upper bound.
     ///
-    /// Note this *currently* only supports ranges that have a max of `2^(24+log_2(column_count))` due to `ScrollArea` limitations.
+    /// Ranges can span the entire [`Address`] space.
     address_ranges: BTreeMap<String, Range<Address>>,
     /// A collection of options relevant for the `MemoryEditor` window.
     /// Can optionally be serialized/deserialized with `serde`
@@ -35,6 +61,20 @@ pub struct MemoryEditor {
     frame_data: BetweenFrameData,
     /// The visible range of addresses from the last frame.
     visible_range: Range<Address>,
+    /// Snapshots of the visible rows, to highlight values which changed since the previous frame.
+    change_tracker: ChangeTracker,
+    /// The memory read during the current frame, to ensure every address is only read once per frame.
+    memory_cache: MemoryCache,
+    /// All writes made through the editor, for undo/redo.
+    history: EditHistory,
+    /// The memory searched by a worker thread, if searches shouldn't run on the UI thread.
+    search_source: Option<SharedMemorySource>,
+    /// The custom character encodings which can be selected for the text sidebar, by name.
+    character_tables: BTreeMap<String, CharacterTable>,
+    /// Named copies of memory which the current memory can be compared against.
+    snapshots: Vec<MemorySnapshot>,
+    /// The memory of the right pane of a side-by-side comparison.
+    comparison: Comparison,
 }
 
 impl MemoryEditor {
@@ -51,15 +91,24 @@ impl MemoryEditor {
     /// let mut memory_editor = MemoryEditor::new().with_address_range("Memory", 0..0xFF);
     ///
     /// // Show a read-only window
-    /// memory_editor.window_ui_read_only(&ctx, &mut is_open, &mut memory_base, |mem, addr| mem[addr].into());
+    /// memory_editor.window_ui_read_only(&ctx, &mut is_open, &mut memory_base, |mem, addr| mem[addr as usize].into());
     /// ```
     pub fn new() -> Self {
+        let options = MemoryEditorOptions::default();
+
         MemoryEditor {
             window_name: "Memory Editor".to_string(),
             address_ranges: BTreeMap::new(),
-            options: Default::default(),
+            history: EditHistory::new(options.undo_history_length),
+            comparison: Comparison::new(options.undo_history_length),
+            options,
             frame_data: Default::default(),
             visible_range: Default::default(),
+            change_tracker: Default::default(),
+            memory_cache: Default::default(),
+            search_source: None,
+            character_tables: BTreeMap::new(),
+            snapshots: Vec::new(),
         }
     }
 
@@ -70,6 +119,78 @@ impl MemoryEditor {
         &self.visible_range
     }
 
+    /// Returns the currently selected range of addresses, if there is a selection.
+    ///
+    /// A selection can be made by dragging over the values, Shift+clicking, or by using Shift+arrow keys.
+    pub fn selection(&self) -> Option<Range<Address>> {
+        self.frame_data.selection.map(|selection| selection.range())
+    }
+
+    /// Select the given range of addresses, or clear the selection with `None`.
+    ///
+    /// Empty ranges clear the selection as well.
+    pub fn set_selection(&mut self, range: Option<Range<Address>>) {
+        match range.filter(|range| !range.is_empty()) {
+            Some(range) => self.frame_data.select(Selection::from_range(&range)),
+            None => {
+                self.frame_data.selection = None;
+                self.frame_data.selected_highlight_address = None;
+            }
+        }
+    }
+
+    /// Returns the undo/redo history of all writes made through the editor.
+    pub fn history(&self) -> &EditHistory {
+        &self.history
+    }
+
+    /// Clear the undo/redo history, e.g. after the memory was reset.
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Undo the most recent write (or group of writes, like a paste) made through the editor.
+    ///
+    /// Also available through `Ctrl+Z` in the UI. Returns `false` if there was nothing to undo, or if the most recent
+    /// group isn't undoable (see [`history::EditGroup::irreversible_reason`]).
+    pub fn undo<S: MemorySource + ?Sized>(&mut self, source: &mut S) -> bool {
+        self.history.undo(source, &mut self.memory_cache)
+    }
+
+    /// Redo the most recently undone write (or group of writes).
+    ///
+    /// Also available through `Ctrl+Y` or `Ctrl+Shift+Z` in the UI. Returns `false` if there was nothing to redo.
+    pub fn redo<S: MemorySource + ?Sized>(&mut self, source: &mut S) -> bool {
+        self.history.redo(source, &mut self.memory_cache)
+    }
+
+    /// Returns all snapshots, in the order in which they were taken.
+    ///
+    /// Snapshots can be taken in the UI, or with [`MemorySnapshot::capture`] and [`Self::add_snapshot`].
+    pub fn snapshots(&self) -> &[MemorySnapshot] {
+        &self.snapshots
+    }
+
+    /// Add a snapshot, e.g. one which was restored with `serde`. It can then be compared against in the UI.
+    pub fn add_snapshot(&mut self, snapshot: MemorySnapshot) {
+        self.snapshots.push(snapshot);
+    }
+
+    /// Remove and return the snapshot at `index`, if it exists.
+    pub fn remove_snapshot(&mut self, index: usize) -> Option<MemorySnapshot> {
+        if index >= self.snapshots.len() {
+            return None;
+        }
+
+        match &mut self.frame_data.snapshot_diff {
+            Some(diff) if diff.snapshot == index => self.frame_data.snapshot_diff = None,
+            Some(diff) if diff.snapshot > index => diff.snapshot -= 1,
+            _ => {}
+        }
+
+        Some(self.snapshots.remove(index))
+    }
+
     /// Create a read-only window and render the memory editor contents within.
     ///
     /// If you want to make your own window/container to be used for the editor contents, you can use [`Self::draw_editor_contents`].
@@ -80,8 +201,8 @@ impl MemoryEditor {
     /// * `ctx` - The `egui` context.
     /// * `mem` - The memory from which to read.
     /// * `read_fn` - Any closure which takes in a reference to the memory and an address and returns a `u8` value. It can
-    /// return `None` if the data at the specified address is not available for whatever reason. This will then be rendered
-    /// as `--` (See [`MemoryEditorOptions::none_display_value`])
+    ///   return `None` if the data at the specified address is not available for whatever reason. This will then be rendered
+    ///   as `--` (See [`MemoryEditorOptions::none_display_value`])
     pub fn window_ui_read_only<T: ?Sized>(
         &mut self,
         ctx: &Context,
@@ -89,10 +210,7 @@ impl MemoryEditor {
         mem: &mut T,
         read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
     ) {
-        // This needs to exist due to the fact we want to use generics, and `Option` needs to know the size of its contents.
-        type DummyWriteFunction<T> = fn(&mut T, Address, u8);
-
-        self.window_ui_impl(ctx, is_open, mem, read_fn, None::<DummyWriteFunction<T>>);
+        self.window_ui_with_source(ctx, is_open, &mut ClosureSource::read_only(mem, read_fn));
     }
 
     /// Create a window and render the memory editor contents within.
@@ -105,8 +223,8 @@ impl MemoryEditor {
     /// * `ctx` - The `egui` context.
     /// * `mem` - The memory from which to read.
     /// * `read_fn` - Any closure which takes in a reference to the memory and an address and returns a `u8` value. It can
-    /// return `None` if the data at the specified address is not available for whatever reason. This will then be rendered
-    /// as `--` (See [`MemoryEditorOptions::none_display_value`])
+    ///   return `None` if the data at the specified address is not available for whatever reason. This will then be rendered
+    ///   as `--` (See [`MemoryEditorOptions::none_display_value`])
     /// * `write_fn` - Any closure which can take a reference to the memory, an address, and the value to write.
     pub fn window_ui<T: ?Sized>(
         &mut self,
@@ -116,16 +234,20 @@ impl MemoryEditor {
         read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
         write_fn: impl FnMut(&mut T, Address, u8),
     ) {
-        self.window_ui_impl(ctx, is_open, mem, read_fn, Some(write_fn));
+        self.window_ui_with_source(ctx, is_open, &mut ClosureSource::new(mem, read_fn, write_fn));
     }
 
-    fn window_ui_impl<T: ?Sized>(
+    /// Create a window and render the memory editor contents within, using a [`MemorySource`] for all memory access.
+    ///
+    /// Whether an address can be edited is determined by [`MemorySource::is_writable`].
+    ///
+    /// If you want to make your own window/container to be used for the editor contents, you can use
+    /// [`Self::draw_editor_contents_with_source`].
+    pub fn window_ui_with_source<S: MemorySource + ?Sized>(
         &mut self,
         ctx: &Context,
         is_open: &mut bool,
-        mem: &mut T,
-        read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
-        write_fn: Option<impl FnMut(&mut T, Address, u8)>,
+        source: &mut S,
     ) {
         Window::new(self.window_name.clone())
             .open(is_open)
@@ -134,7 +256,7 @@ impl MemoryEditor {
             .resizable(true)
             .show(ctx, |ui| {
                 self.shrink_window_ui(ui);
-                self.draw_editor_contents_impl(ui, mem, read_fn, write_fn);
+                self.draw_editor_contents_with_source(ui, source);
             });
     }
 
@@ -151,10 +273,7 @@ impl MemoryEditor {
         mem: &mut T,
         read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
     ) {
-        // This needs to exist due to the fact we want to use generics, and `Option` needs to know the size of its contents.
-        type DummyWriteFunction<T> = fn(&mut T, Address, u8);
-
-        self.draw_editor_contents_impl(ui, mem, read_fn, None::<DummyWriteFunction<T>>);
+        self.draw_editor_contents_with_source(ui, &mut ClosureSource::read_only(mem, read_fn));
     }
 
     /// Draws the actual memory viewer/editor.
@@ -171,28 +290,45 @@ impl MemoryEditor {
         read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
         write_fn: impl FnMut(&mut T, Address, u8),
     ) {
-        self.draw_editor_contents_impl(ui, mem, read_fn, Some(write_fn));
+        self.draw_editor_contents_with_source(ui, &mut ClosureSource::new(mem, read_fn, write_fn));
     }
 
-    fn draw_editor_contents_impl<T: ?Sized>(
+    /// Draws the actual memory viewer/editor, using a [`MemorySource`] for all memory access.
+    ///
+    /// Can be included in whatever container you want.
+    ///
+    /// Use [`Self::window_ui_with_source`] if you want to have a window with the contents instead.
+    pub fn draw_editor_contents_with_source<S: MemorySource + ?Sized>(&mut self, ui: &mut Ui, source: &mut S) {
+        self.draw_contents(ui, source, None::<&mut S>);
+    }
+
+    /// Draws the editor for the `source`, with the `right` source next to it if this is a side-by-side comparison.
+    fn draw_contents<S: MemorySource + ?Sized, R: MemorySource + ?Sized>(
         &mut self,
         ui: &mut Ui,
-        mem: &mut T,
-        mut read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
-        mut write_fn: Option<impl FnMut(&mut T, Address, u8)>,
+        source: &mut S,
+        mut right: Option<&mut R>,
     ) {
         assert!(
             !self.address_ranges.is_empty(),
             "At least one address range needs to be added to render the contents!"
         );
 
-        self.draw_options_area(ui, mem, &mut read_fn);
+        let address_space = self.address_ranges.get(&self.options.selected_address_range).unwrap();
+        self.memory_cache.clear(address_space, self.options.row_length());
+        self.comparison.is_shown = right.is_some();
+
+        self.draw_options_area(ui, source);
 
         ui.separator();
 
+        if let Some(right) = right.as_deref_mut() {
+            self.draw_comparison_toolbar(ui, right);
+        }
+
+        let column_count = self.options.row_length();
         let MemoryEditorOptions {
             show_ascii,
-            column_count,
             address_text_colour,
             highlight_text_colour,
             selected_address_range,
@@ -202,31 +338,106 @@ impl MemoryEditor {
 
         let line_height = self.get_line_height(ui);
         let address_space = self.address_ranges.get(&selected_address_range).unwrap().clone();
-        // This is janky, but can't think of a better way.
-        let address_characters = format!("{:X}", address_space.end - 1).chars().count();
-        let max_lines = (address_space.len() + column_count - 1) / column_count; // div_ceil
+        let address_characters = utilities::address_characters(&address_space);
+        // Lines are counted as `u64`, an address space can have more lines than fit in a `usize` on 32-bit hosts.
+        let line_length = column_count as Address;
+        let max_lines = address_space.length().div_ceil(line_length);
+
+        // The options area may have changed the range or column count, in which case the cache has to be rebuilt.
+        self.memory_cache.set_layout(&address_space, column_count);
 
         // For when we're editing memory, don't use the `Response` object as that would screw over downward scrolling.
         self.handle_keyboard_edit_input(&address_space, ui.ctx());
-
-        let mut scroll = ScrollArea::vertical()
-            .id_salt(selected_address_range)
-            .max_height(f32::INFINITY)
-            .auto_shrink([false, true]);
+        self.handle_paste_input(ui.ctx(), source, &address_space);
+        self.handle_history_input(ui.ctx(), source);
+        self.handle_search_input(ui.ctx(), source, &address_space);
+        self.handle_value_scan(ui.ctx(), source);
+
+        // Rows can end up taller than the text itself (e.g. due to the minimum interaction size), so prefer the height
+        // measured in the previous frame.
+        let row_height = self
+            .frame_data
+            .previous_frame_row_height
+            .max(line_height + ui.spacing().item_spacing.y);
+        let visible_lines = ((ui.available_height() / row_height).floor() as u64).clamp(1, max_lines.max(1));
+        let max_top_line = max_lines.saturating_sub(visible_lines);
+
+        // We don't use a `ScrollArea` as its `f32` offsets can't represent the rows of large address spaces exactly.
+        // Instead, we only keep track of the first visible line and render the rows after it ourselves.
+        let mut top_line = self
+            .frame_data
+            .top_lines
+            .get(&selected_address_range)
+            .copied()
+            .unwrap_or_default();
 
         // Scroll to the goto area address line.
         if let Some(line) = self.frame_data.goto_address_line.take() {
-            let new_offset = (line_height + ui.spacing().item_spacing.y) * (line as f32);
-            scroll = scroll.vertical_scroll_offset(new_offset);
+            top_line = line;
+        }
+
+        // Keep the address which is being edited in view, e.g. when moving it with the arrow keys.
+        if let Some(address) = self.frame_data.scroll_to_address.take() {
+            if let Some(line) = address
+                .checked_sub(address_space.start)
+                .map(|offset| offset / line_length)
+            {
+                if line < top_line {
+                    top_line = line;
+                } else if line >= top_line.saturating_add(visible_lines) {
+                    top_line = line - visible_lines + 1;
+                }
+            }
         }
 
-        scroll.show_rows(ui, line_height, max_lines, |ui, line_range| {
-            // Persist the visible range for future queries.
-            let start_address_range = address_space.start + (line_range.start * column_count);
-            let end_address_range = address_space.start + (line_range.end * column_count);
-            self.visible_range = start_address_range..end_address_range;
+        if ui.rect_contains_pointer(ui.available_rect_before_wrap()) {
+            top_line = self.frame_data.apply_scroll_delta(ui, top_line, row_height);
+        }
 
-            egui::Grid::new("mem_edit_grid")
+        top_line = top_line.min(max_top_line);
+        let line_range = top_line..(top_line + visible_lines).min(max_lines);
+
+        // Persist the visible range for future queries.
+        let start_address_range = address_space.start.saturating_add(line_range.start * line_length);
+        let end_address_range = address_space
+            .start
+            .saturating_add(line_range.end.saturating_mul(line_length))
+            .min(address_space.end);
+        self.visible_range = start_address_range..end_address_range;
+        // Fetch all visible memory at once, the rows below will only use the cached values.
+        self.memory_cache.fetch(source, self.visible_range.clone());
+
+        // Addresses which can't be peeked should still show the result of an explicit read.
+        if let Some((address, Some(value))) = self.frame_data.last_real_read {
+            if !source.is_peekable(address) {
+                self.memory_cache.set(address, value);
+            }
+        }
+
+        if self.options.highlight_changes {
+            self.change_tracker.update(
+                &self.memory_cache,
+                &address_space,
+                &self.visible_range,
+                column_count,
+                ui.input(|i| i.time),
+                self.options.change_highlight_duration,
+            );
+
+            // Keep the frames coming while the highlights fade out.
+            if self.change_tracker.is_fading() {
+                ui.ctx().request_repaint();
+            }
+        } else {
+            self.change_tracker.clear();
+        }
+
+        if let Some(right) = right.as_deref_mut() {
+            self.fetch_comparison(ui, right, &address_space, column_count);
+        }
+
+        ui.horizontal_top(|ui| {
+            let grid_response = egui::Grid::new("mem_edit_grid")
                 .striped(true)
                 .spacing(Vec2::new(15.0, ui.style().spacing.item_spacing.y))
                 .show(ui, |ui| {
@@ -234,8 +445,8 @@ impl MemoryEditor {
                     ui.style_mut().spacing.item_spacing.x = 3.0;
 
                     for start_row in line_range.clone() {
-                        let start_address = address_space.start + (start_row * column_count);
-                        let line_range = start_address..start_address + column_count;
+                        let start_address = address_space.start + (start_row * line_length);
+                        let line_range = start_address..start_address.saturating_add(line_length);
                         let highlight_in_range = matches!(self.frame_data.selected_highlight_address, Some(address) if line_range.contains(&address));
 
                         let start_text = RichText::new(format!("0x{:01$X}:", start_address, address_characters))
@@ -244,59 +455,177 @@ impl MemoryEditor {
 
                         ui.label(start_text);
 
-                        self.draw_memory_values(ui, mem, &mut read_fn, &mut write_fn, start_address, &address_space);
+                        self.draw_memory_values(ui, source, start_address, &address_space);
 
                         if show_ascii {
-                            self.draw_ascii_sidebar(ui, mem, &mut read_fn, start_address, &address_space);
+                            self.draw_ascii_sidebar(ui, source, start_address, &address_space);
+                        }
+
+                        if let Some(right) = right.as_deref_mut() {
+                            self.draw_comparison_row(ui, right, start_address, &address_space, show_ascii);
                         }
 
                         ui.end_row();
                     }
-                });
+                })
+                .response;
+
+            self.frame_data.previous_frame_grid_rect = Some(grid_response.rect);
+
+            let rows_drawn = line_range.length().max(1) as f32;
+            self.frame_data.previous_frame_row_height =
+                (grid_response.rect.height() + ui.spacing().item_spacing.y) / rows_drawn;
+
+            let scrollbar_height = grid_response.rect.height();
+            top_line = self.draw_scrollbar(ui, scrollbar_height, top_line, max_top_line, visible_lines, max_lines);
+
             // After we've drawn the area we want to resize to we want to save this size for the next frame.
             // In case it has become smaller we'll shrink the window.
             self.frame_data.previous_frame_editor_width = ui.min_rect().width();
         });
+
+        self.frame_data.top_lines.insert(selected_address_range, top_line);
+
+        if let Some(right) = right {
+            self.handle_copy_left_to_right(source, right);
+        }
+        self.handle_pending_copy(ui.ctx(), source, &address_space);
+        self.draw_paste_confirmation(ui.ctx(), source, &address_space);
+        self.draw_fill_dialog(ui.ctx(), source);
     }
 
-    fn draw_memory_values<T: ?Sized>(
+    /// Draw a vertical scrollbar for the given line positions, returning the (possibly dragged) new top line.
+    ///
+    /// The handle position is only a coarse `f64` approximation of the top line, the exact line is kept as an integer
+    /// so that scrolling with the mouse wheel or jumping to an address remains exact for any address space.
+    fn draw_scrollbar(
         &mut self,
         ui: &mut Ui,
-        mem: &mut T,
-        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
-        write_fn: &mut Option<impl FnMut(&mut T, Address, u8)>,
+        height: f32,
+        top_line: u64,
+        max_top_line: u64,
+        visible_lines: u64,
+        max_lines: u64,
+    ) -> u64 {
+        let bar_width = ui.spacing().scroll.bar_width;
+        let (rect, response) = ui.allocate_exact_size(Vec2::new(bar_width, height), Sense::click_and_drag());
+
+        if max_top_line == 0 || !ui.is_rect_visible(rect) {
+            return top_line;
+        }
+
+        let handle_height = (height * (visible_lines as f64 / max_lines as f64) as f32)
+            .max(ui.spacing().scroll.handle_min_length)
+            .min(height);
+        let travel = (height - handle_height).max(1.0);
+        let handle_top = |line: u64| rect.top() + travel * (line as f64 / max_top_line as f64) as f32;
+
+        let mut top_line = top_line;
+
+        if let Some(pointer) = response.interact_pointer_pos() {
+            let grab_offset = *self.frame_data.scrollbar_grab_offset.get_or_insert_with(|| {
+                let handle_top = handle_top(top_line);
+                if (handle_top..=handle_top + handle_height).contains(&pointer.y) {
+                    pointer.y - handle_top
+                } else {
+                    handle_height / 2.0
+                }
+            });
+
+            let fraction = ((pointer.y - grab_offset - rect.top()) / travel).clamp(0.0, 1.0) as f64;
+            top_line = (fraction * max_top_line as f64).round() as u64;
+        } else {
+            self.frame_data.scrollbar_grab_offset = None;
+        }
+
+        let visuals = ui.style().interact(&response);
+        let handle_rect = egui::Rect::from_min_size(
+            egui::pos2(rect.left(), handle_top(top_line)),
+            Vec2::new(bar_width, handle_height),
+        );
+        let painter = ui.painter();
+        painter.rect_filled(rect, visuals.rounding, ui.visuals().extreme_bg_color);
+        painter.rect_filled(handle_rect, visuals.rounding, visuals.bg_fill);
+
+        top_line.min(max_top_line)
+    }
+
+    fn draw_memory_values<S: MemorySource + ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        source: &mut S,
         start_address: Address,
         address_space: &Range<Address>,
     ) {
         let frame_data = &mut self.frame_data;
+        let memory_cache = &mut self.memory_cache;
+        let history = &mut self.history;
         let options = &self.options;
-        let mut read_only = frame_data.selected_edit_address.is_none() || write_fn.is_none();
+        let now = ui.input(|i| i.time);
+        let change_intensity = |address| {
+            self.change_tracker
+                .intensity(address, now, options.change_highlight_duration)
+        };
+        let diff_snapshot = frame_data
+            .snapshot_diff
+            .as_ref()
+            .and_then(|diff| self.snapshots.get(diff.snapshot));
+        // While the right pane of a comparison is drawn, this holds the memory of the left pane.
+        let compared_cache = self.comparison.is_shown.then_some(&self.comparison.memory_cache);
+        let differs = |address: Address, value: Option<u8>| {
+            diff_snapshot.is_some_and(|snapshot| snapshot.is_changed(address, value))
+                || compared_cache.is_some_and(|other| other.get(address) != value)
+        };
+        let is_right_pane = self.comparison.is_drawing_right;
+        let cell_width = options.cell_width();
+        let row_length = options.row_length();
+        // Values wider than 8 bytes get a grid column of their own.
+        let grid_column_length = (8 / cell_width).max(1) * cell_width;
 
-        // div_ceil
-        for grid_column in 0..(options.column_count + 7) / 8 {
-            let start_address = start_address + 8 * grid_column;
+        for grid_column in 0..row_length.div_ceil(grid_column_length) {
+            let start_address = start_address.saturating_add((grid_column_length * grid_column) as Address);
 
-            // Each grid column is 8 bytes, where each byte is one 'sub-column'.
+            // Each grid column is (up to) 8 bytes, where each cell (a byte, a word, or a value) is one 'sub-column'.
             ui.horizontal(|ui| {
-                let column_count = (options.column_count - 8 * grid_column).min(8);
+                let column_length = (row_length - grid_column_length * grid_column).min(grid_column_length);
 
-                for column_index in 0..column_count {
-                    let memory_address = start_address + column_index;
+                for column_offset in (0..column_length).step_by(cell_width) {
+                    let memory_address = start_address.saturating_add(column_offset as Address);
 
                     if !address_space.contains(&memory_address) {
                         break;
                     }
 
-                    let mem_val: Option<u8> = read_fn(mem, memory_address);
+                    let cell = memory_address..memory_address.saturating_add(cell_width as Address);
+                    let cell_bytes: Option<Vec<u8>> = cell
+                        .clone()
+                        .map(|address| memory_cache.get(address).filter(|_| address_space.contains(&address)))
+                        .collect();
                     // If the read function can't read for whatever reason we'll just assume some temporary `--` value.
-                    let label_text = match mem_val {
-                        Some(val) => format!("{:02X}", val),
-                        None => options.none_display_value.clone(),
+                    let label_text = match (&cell_bytes, options.typed_grid) {
+                        (Some(bytes), Some(typed_grid)) => utilities::slice_to_cell_string(typed_grid, bytes),
+                        (Some(bytes), None) => {
+                            let value = utilities::bytes_to_word(bytes, options.word_endianness);
+                            options.cell_radix.format(value, cell_width)
+                        }
+                        (None, _) => options.none_display_value.clone(),
+                    };
+                    // Keep the columns of typed values aligned.
+                    let label_text = match options.typed_grid {
+                        Some(typed_grid) => {
+                            format!(
+                                "{:>1$}",
+                                label_text,
+                                typed_grid.selected_data_format.max_display_length()
+                            )
+                        }
+                        None => label_text,
                     };
 
                     // Memory Value Labels
-                    if !read_only
-                        && matches!(frame_data.selected_edit_address, Some(address) if address == memory_address)
+                    if frame_data.active_pane == Pane::Hex
+                        && matches!(frame_data.selected_edit_address, Some(address) if cell.contains(&address))
+                        && source.is_writable(memory_address)
                     {
                         // For Editing
                         let response = ui.add(
@@ -313,64 +642,120 @@ impl MemoryEditor {
                             response.request_focus();
                         }
 
-                        // Filter out any non Hex-Digit, there doesn't seem to be a method in TextEdit for this.
-                        frame_data
-                            .selected_edit_address_string
-                            .retain(|c| c.is_ascii_hexdigit());
-
-                        // Don't want more than 2 digits
-                        if frame_data.selected_edit_address_string.chars().count() >= 2 {
-                            let next_address = memory_address + 1;
-                            let new_value = u8::from_str_radix(&frame_data.selected_edit_address_string[0..2], 16);
+                        // Filter out any invalid digit, there doesn't seem to be a method in TextEdit for this.
+                        // Typed values are checked once they're confirmed, as floats can be written in many ways.
+                        let radix = options.cell_radix;
+                        if options.typed_grid.is_none() {
+                            let mut index = 0;
+                            frame_data.selected_edit_address_string.retain(|c| {
+                                let keep = radix.is_digit(c, index);
+                                index += keep as usize;
+                                keep
+                            });
+                        }
 
-                            if let Ok(value) = new_value {
-                                if let Some(write_fns) = write_fn.as_mut() {
-                                    write_fns(mem, memory_address, value);
+                        // Move on once all digits are typed, or when a shorter value is confirmed with enter.
+                        let edit_string = &frame_data.selected_edit_address_string;
+                        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        let is_complete = options.typed_grid.is_none() && radix.is_complete(edit_string, cell_width);
+
+                        if is_complete || (submitted && !edit_string.is_empty()) {
+                            let next_address = cell.end;
+                            let bytes = match options.typed_grid {
+                                Some(typed_grid) => utilities::decimal_string_to_bytes(typed_grid, edit_string).ok(),
+                                None => radix
+                                    .parse(edit_string, cell_width)
+                                    .map(|value| utilities::word_to_bytes(value, cell_width, options.word_endianness)),
+                            };
+
+                            match bytes {
+                                Some(bytes) => {
+                                    // The last word may extend past the end of the address space.
+                                    let writes = cell.clone().zip(bytes).filter(|(a, _)| address_space.contains(a));
+                                    history.write(source, memory_cache, "Edit", writes);
+                                    frame_data.set_selected_edit_address(Some(next_address), address_space);
+                                }
+                                // Out of range, or not a number, let the user try again.
+                                None => {
+                                    frame_data.selected_edit_address_string.clear();
+                                    response.request_focus();
                                 }
                             }
-
-                            frame_data.set_selected_edit_address(Some(next_address), address_space);
                         } else if !response.has_focus() {
                             // We use has_focus() instead of response.inner.lost_focus() due to the latter
                             // having a bug where it doesn't detect if it lost focus when you scroll.
                             frame_data.set_selected_edit_address(None, address_space);
-                            read_only = true;
                         }
                     } else {
                         // Read-only values.
                         let mut text = RichText::new(label_text).text_style(options.memory_editor_text_style.clone());
 
-                        if options.show_zero_colour && (matches!(mem_val, Some(val) if val == 0) || mem_val.is_none()) {
-                            text = text.color(options.zero_colour);
-                        } else {
-                            text = text.color(ui.style().visuals.text_color());
-                        };
+                        let mut colour = ui.style().visuals.text_color();
+                        if options.show_zero_colour
+                            && cell_bytes.map_or(true, |bytes| bytes.iter().all(|&val| val == 0))
+                        {
+                            colour = options.zero_colour;
+                        }
 
-                        if frame_data.should_highlight(memory_address) {
+                        let change = cell.clone().filter_map(&change_intensity).reduce(f32::max);
+                        if let Some(intensity) = change {
+                            colour = colour.lerp_to_gamma(options.change_highlight_colour, intensity);
+                        }
+                        text = text.color(colour);
+
+                        if cell.clone().any(|address| frame_data.should_highlight(address)) {
                             text = text.color(options.highlight_text_colour);
                         }
 
-                        if frame_data.should_subtle_highlight(memory_address, options.data_preview.selected_data_format)
-                        {
+                        let subtle_highlight = cell.clone().any(|address| {
+                            frame_data.should_subtle_highlight(
+                                address,
+                                options.data_preview.selected_data_format,
+                                memory_cache,
+                            )
+                        });
+                        if subtle_highlight {
                             text = text.background_color(ui.style().visuals.code_bg_color);
                         }
 
-                        let response = Label::new(text).sense(Sense::click()).ui(ui);
+                        if cell.clone().any(|address| differs(address, memory_cache.get(address))) {
+                            text = text.background_color(options.diff_colour);
+                        }
+
+                        if cell.clone().any(|address| frame_data.is_selected(address)) {
+                            text = text.background_color(ui.style().visuals.selection.bg_fill);
+                        }
+
+                        let mut response = Label::new(text).selectable(false).sense(Sense::click_and_drag()).ui(ui);
                         // For use with the `Edit` widget, keep track of the size of ordinary display to keep column jitter at bay
                         frame_data.previous_frame_text_edit_size = response.rect.width();
 
-                        // Right click always selects.
-                        if response.secondary_clicked() {
+                        frame_data.handle_selection_input(ui, &response, cell.clone(), Pane::Hex);
+
+                        if response.hovered() {
+                            if let Some(region) = source.region(memory_address) {
+                                response = response.on_hover_text(format!("{} ({:#X?})", region.name, region.range));
+                            }
+                        }
+
+                        // Right click always selects, unless it's part of a larger selection we want to keep.
+                        if response.secondary_clicked() && !frame_data.is_selected(memory_address) {
                             frame_data.set_highlight_address(memory_address);
                         }
 
-                        // Left click depends on read only mode.
-                        if response.clicked() {
-                            if write_fn.is_some() {
-                                frame_data.set_selected_edit_address(Some(memory_address), address_space);
+                        response.context_menu(|ui| {
+                            if is_right_pane {
+                                Self::draw_comparison_context_menu(ui, frame_data, memory_address);
                             } else {
-                                frame_data.set_highlight_address(memory_address);
+                                Self::draw_address_context_menu(ui, frame_data, memory_cache, source, memory_address);
                             }
+                        });
+
+                        // Left click depends on read only mode, in read-only mode the selection is all we need.
+                        // Shift+click only extends the selection.
+                        if response.clicked() && source.is_writable(memory_address) && !ui.input(|i| i.modifiers.shift)
+                        {
+                            frame_data.set_selected_edit_address(Some(memory_address), address_space);
                         }
                     }
                 }
@@ -378,49 +763,250 @@ impl MemoryEditor {
         }
     }
 
-    fn draw_ascii_sidebar<T: ?Sized>(
+    fn draw_ascii_sidebar<S: MemorySource + ?Sized>(
         &mut self,
         ui: &mut Ui,
-        mem: &mut T,
-        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        source: &mut S,
         start_address: Address,
         address_space: &Range<Address>,
     ) {
+        let frame_data = &mut self.frame_data;
+        let memory_cache = &mut self.memory_cache;
+        let history = &mut self.history;
         let options = &self.options;
+        let now = ui.input(|i| i.time);
+        let change_intensity = |address| {
+            self.change_tracker
+                .intensity(address, now, options.change_highlight_duration)
+        };
+        let diff_snapshot = frame_data
+            .snapshot_diff
+            .as_ref()
+            .and_then(|diff| self.snapshots.get(diff.snapshot));
+        // While the right pane of a comparison is drawn, this holds the memory of the left pane.
+        let compared_cache = self.comparison.is_shown.then_some(&self.comparison.memory_cache);
+        let differs = |address: Address, value: Option<u8>| {
+            diff_snapshot.is_some_and(|snapshot| snapshot.is_changed(address, value))
+                || compared_cache.is_some_and(|other| other.get(address) != value)
+        };
+        let is_right_pane = self.comparison.is_drawing_right;
+        let character_tables = &self.character_tables;
 
         ui.horizontal(|ui| {
             ui.add(egui::Separator::default().vertical().spacing(3.0));
             ui.style_mut().spacing.item_spacing.x = 0.0;
 
             ui.horizontal(|ui| {
-                for i in 0..options.column_count {
-                    let memory_address = start_address + i;
+                let decoder = encoding::selected_decoder(options, character_tables);
+                let alignment = decoder.alignment().max(1);
+                // The remaining bytes of the previous character, and what to show for them. Characters which start in
+                // the previous row aren't shown.
+                let misalignment = ((start_address - address_space.start) % alignment as Address) as usize;
+                let mut remaining = (alignment - misalignment) % alignment;
+                let mut filler = ".";
+
+                for i in 0..options.row_length() {
+                    let memory_address = start_address.saturating_add(i as Address);
 
                     if !address_space.contains(&memory_address) {
                         break;
                     }
 
-                    let mem_val: u8 = read_fn(mem, memory_address).unwrap_or(0);
-                    // Check if it's a printable ASCII character
-                    let character = if !(32..128).contains(&mem_val) {
-                        '.'
+                    let character = if remaining > 0 {
+                        remaining -= 1;
+                        filler.to_string()
                     } else {
-                        mem_val as char
+                        let bytes: Vec<u8> = (memory_address
+                            ..memory_address.saturating_add(decoder.max_length() as Address))
+                            .map_while(|address| memory_cache.get(address).filter(|_| address_space.contains(&address)))
+                            .collect();
+
+                        match decoder.decode(&bytes) {
+                            // Multi-byte characters are shown at their first byte.
+                            Some((character, length)) => {
+                                remaining = length.saturating_sub(1);
+                                filler = " ";
+                                character
+                            }
+                            None => {
+                                remaining = alignment - 1;
+                                filler = ".";
+                                ".".to_string()
+                            }
+                        }
                     };
+
+                    if frame_data.active_pane == Pane::Ascii
+                        && matches!(frame_data.selected_edit_address, Some(address) if address == memory_address)
+                        && source.is_writable(memory_address)
+                    {
+                        let response = ui.add(
+                            TextEdit::singleline(&mut frame_data.selected_edit_address_string)
+                                .desired_width(frame_data.previous_frame_ascii_text_edit_size)
+                                .margin(Margin::symmetric(0., 0.))
+                                .font(options.memory_editor_ascii_text_style.clone())
+                                .hint_text(character.clone())
+                                .id_source(frame_data.selected_edit_address),
+                        );
+
+                        if frame_data.selected_edit_address_request_focus {
+                            frame_data.selected_edit_address_request_focus = false;
+                            response.request_focus();
+                        }
+
+                        // Only characters which the encoding can represent can be typed.
+                        let typed = frame_data
+                            .selected_edit_address_string
+                            .chars()
+                            .filter(|c| !c.is_control())
+                            .find_map(|c| decoder.encode(c));
+                        frame_data.selected_edit_address_string.clear();
+
+                        if let Some(typed) = typed {
+                            let next_address = memory_address.saturating_add(typed.len() as Address);
+                            let writes = (memory_address..).zip(typed).filter(|(a, _)| address_space.contains(a));
+                            history.write(source, memory_cache, "Edit", writes);
+                            frame_data.set_selected_edit_address(Some(next_address), address_space);
+                        } else if !response.has_focus() {
+                            frame_data.set_selected_edit_address(None, address_space);
+                        }
+
+                        continue;
+                    }
+
                     let mut text = RichText::new(character).text_style(options.memory_editor_ascii_text_style.clone());
 
-                    if self.frame_data.should_highlight(memory_address) {
+                    if let Some(intensity) = change_intensity(memory_address) {
+                        let colour = ui.style().visuals.text_color();
+                        text = text.color(colour.lerp_to_gamma(options.change_highlight_colour, intensity));
+                    }
+
+                    if frame_data.should_highlight(memory_address) {
                         text = text
-                            .color(self.options.highlight_text_colour)
+                            .color(options.highlight_text_colour)
                             .background_color(ui.style().visuals.code_bg_color);
                     }
 
-                    ui.label(text);
+                    if differs(memory_address, memory_cache.get(memory_address)) {
+                        text = text.background_color(options.diff_colour);
+                    }
+
+                    if frame_data.is_selected(memory_address) {
+                        text = text.background_color(ui.style().visuals.selection.bg_fill);
+                    }
+
+                    let response = Label::new(text).selectable(false).sense(Sense::click_and_drag()).ui(ui);
+                    frame_data.previous_frame_ascii_text_edit_size = response.rect.width();
+                    frame_data.handle_selection_input(ui, &response, memory_address..memory_address + 1, Pane::Ascii);
+
+                    if response.secondary_clicked() && !frame_data.is_selected(memory_address) {
+                        frame_data.set_highlight_address(memory_address);
+                    }
+
+                    response.context_menu(|ui| {
+                        if is_right_pane {
+                            Self::draw_comparison_context_menu(ui, frame_data, memory_address);
+                        } else {
+                            Self::draw_address_context_menu(ui, frame_data, memory_cache, source, memory_address);
+                        }
+                    });
+
+                    if response.clicked() && source.is_writable(memory_address) && !ui.input(|i| i.modifiers.shift) {
+                        frame_data.set_selected_edit_address(Some(memory_address), address_space);
+                    }
                 }
             });
         });
     }
 
+    /// The context menu for a value in either the hex or the ASCII area.
+    fn draw_address_context_menu<S: MemorySource + ?Sized>(
+        ui: &mut Ui,
+        frame_data: &mut BetweenFrameData,
+        memory_cache: &mut MemoryCache,
+        source: &mut S,
+        memory_address: Address,
+    ) {
+        // Copy the selection, or just the address the menu was opened on if nothing is selected.
+        let copy_range = frame_data
+            .selection
+            .map_or(memory_address..memory_address + 1, |selection| selection.range());
+
+        ui.menu_button("Copy as", |ui| {
+            for format in CopyFormat::iter() {
+                if ui.button(format.name()).clicked() {
+                    frame_data.pending_copy = Some((format, copy_range.clone()));
+                    ui.close_menu();
+                }
+            }
+        });
+
+        if ui.button("Fill selection…").clicked() {
+            frame_data.fill.range = Some(copy_range.clone());
+            ui.close_menu();
+        }
+
+        let read_button = ui
+            .button("Read value")
+            .on_hover_text("Read the value as the hardware would, which may have side effects");
+
+        if read_button.clicked() {
+            let value = source.read(memory_address);
+            frame_data.last_real_read = Some((memory_address, value));
+
+            if let Some(value) = value {
+                memory_cache.set(memory_address, value);
+            }
+
+            ui.close_menu();
+        }
+    }
+
+    /// Copy any range requested by the context menu or `Ctrl+C` to the clipboard.
+    fn handle_pending_copy<S: MemorySource + ?Sized>(
+        &mut self,
+        ctx: &Context,
+        source: &mut S,
+        address_space: &Range<Address>,
+    ) {
+        if let Some((format, range)) = self.frame_data.pending_copy.take() {
+            if range.length() > clipboard::MAX_COPY_LENGTH as Address {
+                self.frame_data.copy_message = Some(format!(
+                    "Copies are limited to {} MiB, select a smaller range",
+                    clipboard::MAX_COPY_LENGTH / (1024 * 1024)
+                ));
+            } else {
+                let bytes = memory_cache::peek_range(source, range.clone());
+                ctx.copy_text(clipboard::format_bytes(
+                    format,
+                    range.start,
+                    &bytes,
+                    address_space,
+                    &self.options,
+                ));
+            }
+        }
+
+        let Some(message) = &self.frame_data.copy_message else {
+            return;
+        };
+        let mut is_open = true;
+
+        Window::new("Copy failed")
+            .id(egui::Id::new(&self.window_name).with("copy_message"))
+            .open(&mut is_open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(message);
+            });
+
+        if !is_open {
+            self.frame_data.copy_message = None;
+        }
+    }
+
     /// Return the line height for the current provided `Ui` and selected `TextStyle`s
     fn get_line_height(&self, ui: &mut Ui) -> f32 {
         let address_size = ui.text_style_height(&self.options.memory_editor_address_text_style);
@@ -436,33 +1022,129 @@ impl MemoryEditor {
         ui.set_max_width(self.frame_data.previous_frame_editor_width);
     }
 
-    /// Check for arrow keys when we're editing a memory value at an address.
+    /// Check for `Ctrl+Z`, `Ctrl+Y` and `Ctrl+Shift+Z` when we're editing or the selection has keyboard focus.
+    fn handle_history_input<S: MemorySource + ?Sized>(&mut self, ctx: &Context, source: &mut S) {
+        use egui::{Key, Modifiers};
+
+        self.history.set_max_length(self.options.undo_history_length);
+
+        if self.frame_data.selected_edit_address.is_some() || self.frame_data.has_keyboard_focus {
+            // Consume the keys, so the `TextEdit` of the edit cursor doesn't try to undo its own contents.
+            // Redo is checked first, as `consume_key` ignores additional shift modifiers.
+            let (redo, undo) = ctx.input_mut(|i| {
+                let redo = i.consume_key(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z)
+                    || i.consume_key(Modifiers::COMMAND, Key::Y);
+                (redo, i.consume_key(Modifiers::COMMAND, Key::Z))
+            });
+
+            if redo {
+                self.redo(source);
+            } else if undo && !self.undo(source) {
+                // Nothing can be undone past a group which isn't undoable, so let the user know why.
+                let last_group = self.history.undo_entries().next_back();
+                self.frame_data.history_message = last_group.and_then(|group| {
+                    let reason = group.irreversible_reason.as_ref()?;
+                    Some(format!("\"{}\" can't be undone, {}", group.description, reason))
+                });
+            }
+        }
+
+        let Some(message) = &self.frame_data.history_message else {
+            return;
+        };
+        let mut is_open = true;
+
+        Window::new("Undo failed")
+            .id(egui::Id::new(&self.window_name).with("history_message"))
+            .open(&mut is_open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(message);
+            });
+
+        if !is_open {
+            self.frame_data.history_message = None;
+        }
+    }
+
+    /// Check for arrow keys when we're editing a memory value at an address, or when the selection has keyboard focus.
+    ///
+    /// Holding shift will extend the selection instead of moving it.
     fn handle_keyboard_edit_input(&mut self, address_range: &Range<Address>, ctx: &Context) {
         use egui::Key::*;
         const KEYS: [egui::Key; 4] = [ArrowLeft, ArrowRight, ArrowDown, ArrowUp];
 
-        let Some(current_address) = self.frame_data.selected_edit_address else {
+        let frame_data = &mut self.frame_data;
+        let editing_address = frame_data.selected_edit_address;
+
+        if editing_address.is_none() {
+            // Any click outside the editor, or another widget taking focus, means the arrow keys aren't meant for us.
+            let grid_rect = frame_data.previous_frame_grid_rect;
+            let pressed_elsewhere = ctx.input(|i| {
+                i.pointer.any_pressed()
+                    && i.pointer
+                        .interact_pos()
+                        .is_some_and(|pos| !grid_rect.is_some_and(|rect| rect.contains(pos)))
+            });
+
+            if pressed_elsewhere || ctx.memory(|mem| mem.focused().is_some()) {
+                frame_data.has_keyboard_focus = false;
+            }
+        }
+
+        if editing_address.is_some() || frame_data.has_keyboard_focus {
+            if let Some(selection) = frame_data.selection {
+                if ctx.input(|i| i.events.iter().any(|event| matches!(event, egui::Event::Copy))) {
+                    frame_data.pending_copy = Some((self.options.copy_format, selection.range()));
+                }
+            }
+        }
+
+        let selection_cursor = frame_data
+            .selection
+            .filter(|_| frame_data.has_keyboard_focus)
+            .map(|selection| selection.cursor);
+        let Some(current_address) = editing_address.or(selection_cursor) else {
             return;
         };
 
+        // The edit cursor moves a whole word at a time in the hex area.
+        let step = if frame_data.active_pane == Pane::Hex {
+            self.options.cell_width() as Address
+        } else {
+            1
+        };
         let key_pressed = KEYS.iter().find(|&&k| ctx.input(|i| i.key_pressed(k)));
         if let Some(key) = key_pressed {
             let next_address = match key {
-                ArrowDown => current_address + self.options.column_count,
-                ArrowLeft => current_address.saturating_sub(1),
-                ArrowRight => current_address.saturating_add(1),
-                ArrowUp => {
-                    if current_address < self.options.column_count {
-                        0
-                    } else {
-                        current_address - self.options.column_count
-                    }
-                }
+                ArrowDown => current_address.saturating_add(self.options.row_length() as Address),
+                ArrowLeft => current_address.saturating_sub(step),
+                ArrowRight => current_address.saturating_add(step),
+                ArrowUp => current_address.saturating_sub(self.options.row_length() as Address),
                 _ => unreachable!(),
             };
 
-            self.frame_data
-                .set_selected_edit_address(Some(next_address), address_range);
+            if ctx.input(|i| i.modifiers.shift) {
+                if !address_range.contains(&next_address) {
+                    return;
+                }
+
+                // Growing the selection leaves edit mode, as typing only ever affects a single address.
+                if editing_address.is_some() {
+                    frame_data.set_selected_edit_address(None, address_range);
+                    frame_data.has_keyboard_focus = true;
+                }
+
+                frame_data.extend_selection(next_address);
+                frame_data.scroll_to_address = Some(next_address);
+            } else if editing_address.is_some() {
+                frame_data.set_selected_edit_address(Some(next_address), address_range);
+            } else if address_range.contains(&next_address) {
+                frame_data.select(Selection::new(next_address));
+                frame_data.scroll_to_address = Some(next_address);
+            }
         }
     }
 
@@ -481,7 +1163,7 @@ impl MemoryEditor {
     ///
     /// The first range that is added will be displayed by default when launching the UI.
     ///
-    /// The UI will query your set `read_function` with the values within this `Range`
+    /// The UI will query your set `read_function` (or [`MemorySource`]) with the values within this `Range`
     #[inline]
     #[must_use]
     pub fn with_address_range(mut self, range_name: impl Into<String>, address_range: Range<Address>) -> Self {
@@ -504,6 +1186,43 @@ impl MemoryEditor {
         }
     }
 
+    /// Search the given memory on a worker thread, instead of spreading searches out over multiple frames.
+    ///
+    /// The worker locks the source for one chunk of the search at a time, so it can still be passed to
+    /// [`Self::window_ui_with_source`] every frame. Not available on targets without threads, like the web.
+    #[inline]
+    #[must_use]
+    pub fn with_search_source(mut self, source: SharedMemorySource) -> Self {
+        self.set_search_source(Some(source));
+        self
+    }
+
+    /// Set or clear the memory which is searched on a worker thread.
+    ///
+    /// See also [`Self::with_search_source`]
+    pub fn set_search_source(&mut self, source: Option<SharedMemorySource>) {
+        self.search_source = source;
+    }
+
+    /// Add a custom character encoding, e.g. loaded from a `.tbl` file with [`CharacterTable::load`].
+    ///
+    /// Tables can be selected for every address range in the UI, or through
+    /// [`MemoryEditorOptions::range_character_tables`]. The text pane then decodes and types characters with the table,
+    /// and text searches can use it as well.
+    #[inline]
+    #[must_use]
+    pub fn with_character_table(mut self, table_name: impl Into<String>, table: CharacterTable) -> Self {
+        self.set_character_table(table_name, table);
+        self
+    }
+
+    /// Add or update a custom character encoding.
+    ///
+    /// See also [`Self::with_character_table`]
+    pub fn set_character_table(&mut self, table_name: impl Into<String>, table: CharacterTable) {
+        self.character_tables.insert(table_name.into(), table);
+    }
+
     /// Set the memory options, useful if you use the `persistence` feature.
     #[inline]
     #[must_use]
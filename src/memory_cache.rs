@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::utilities::AddressRangeExt;
+use crate::{Address, MemorySource};
+
+/// The memory which was read during the current frame.
+///
+/// Memory is fetched one row at a time through [`MemorySource::peek_range`], where consecutive missing rows are
+/// fetched with a single call. Addresses which aren't [`MemorySource::is_peekable`] are never touched, and stay
+/// `None`. This ensures every address is only read once per frame, no matter how many parts of the UI (hex cells,
+/// ASCII sidebar, data preview) end up displaying it.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct MemoryCache {
+    address_space: Range<Address>,
+    row_length: usize,
+    /// Row index (relative to the start of the address space) to the values of that row.
+    rows: BTreeMap<u64, Vec<Option<u8>>>,
+}
+
+impl MemoryCache {
+    /// Invalidate all cached memory, should be called at the start of every frame.
+    pub fn clear(&mut self, address_space: &Range<Address>, row_length: usize) {
+        self.address_space = address_space.clone();
+        self.row_length = row_length.max(1);
+        self.rows.clear();
+    }
+
+    /// Change the layout of the rows, only invalidating the cached memory if the layout actually changed.
+    pub fn set_layout(&mut self, address_space: &Range<Address>, row_length: usize) {
+        if self.address_space != *address_space || self.row_length != row_length.max(1) {
+            self.clear(address_space, row_length);
+        }
+    }
+
+    /// Ensure all rows overlapping `range` are cached, reading any missing ones from `source`.
+    pub fn fetch<S: MemorySource + ?Sized>(&mut self, source: &mut S, range: Range<Address>) {
+        let start = range.start.max(self.address_space.start);
+        let end = range.end.min(self.address_space.end);
+
+        if start >= end {
+            return;
+        }
+
+        let first_row = self.row_of(start);
+        let last_row = self.row_of(end - 1);
+        let mut row = first_row;
+
+        while row <= last_row {
+            if self.rows.contains_key(&row) {
+                row += 1;
+                continue;
+            }
+
+            // Gather all consecutive missing rows so that they can be read in one go.
+            let run_start = row;
+            while row <= last_row && !self.rows.contains_key(&row) {
+                row += 1;
+            }
+
+            let read_start = self.row_start(run_start);
+            let read_end = self.row_start(row).min(self.address_space.end).max(read_start);
+            // At most the visible rows are missing, so this always fits in memory.
+            let mut buffer = vec![None; (read_end - read_start) as usize];
+            peek_into(source, read_start, &mut buffer);
+
+            for (i, values) in buffer.chunks(self.row_length).enumerate() {
+                self.rows.insert(run_start + i as u64, values.to_vec());
+            }
+        }
+    }
+
+    /// Return the cached value at `address`, reading its row from `source` if it isn't cached yet.
+    pub fn read<S: MemorySource + ?Sized>(&mut self, source: &mut S, address: Address) -> Option<u8> {
+        if !self.address_space.contains(&address) {
+            return None;
+        }
+
+        self.fetch(source, address..address + 1);
+        self.get(address)
+    }
+
+    /// Return the cached value at `address`, or `None` if it was not fetched.
+    #[inline]
+    pub fn get(&self, address: Address) -> Option<u8> {
+        if !self.address_space.contains(&address) {
+            return None;
+        }
+
+        let offset = address - self.address_space.start;
+        let row_length = self.row_length as Address;
+        self.rows
+            .get(&(offset / row_length))
+            .and_then(|row| row.get((offset % row_length) as usize).copied().flatten())
+    }
+
+    /// Update the cached value at `address`, used after the editor writes to memory.
+    pub fn set(&mut self, address: Address, value: u8) {
+        if !self.address_space.contains(&address) {
+            return;
+        }
+
+        let offset = address - self.address_space.start;
+        let row_length = self.row_length as Address;
+        if let Some(cached) = self
+            .rows
+            .get_mut(&(offset / row_length))
+            .and_then(|row| row.get_mut((offset % row_length) as usize))
+        {
+            *cached = Some(value);
+        }
+    }
+
+    #[inline]
+    fn row_of(&self, address: Address) -> u64 {
+        (address - self.address_space.start) / self.row_length as Address
+    }
+
+    #[inline]
+    fn row_start(&self, row: u64) -> Address {
+        self.address_space
+            .start
+            .saturating_add(row.saturating_mul(self.row_length as Address))
+    }
+}
+
+/// Peek all values in `range`, see [`peek_into`].
+///
+/// The values are collected in memory, so callers have to limit the length of the `range`.
+pub(crate) fn peek_range<S: MemorySource + ?Sized>(source: &mut S, range: Range<Address>) -> Vec<Option<u8>> {
+    let length = usize::try_from(range.length()).expect("Peeked range doesn't fit in memory");
+    let mut buffer = vec![None; length];
+    peek_into(source, range.start, &mut buffer);
+    buffer
+}
+
+/// Peek into `buffer`, skipping over any addresses which can't be peeked.
+pub(crate) fn peek_into<S: MemorySource + ?Sized>(source: &mut S, start: Address, buffer: &mut [Option<u8>]) {
+    let mut offset = 0;
+
+    while offset < buffer.len() {
+        if !source.is_peekable(start + offset as Address) {
+            offset += 1;
+            continue;
+        }
+
+        let run_start = offset;
+        while offset < buffer.len() && source.is_peekable(start + offset as Address) {
+            offset += 1;
+        }
+
+        source.peek_range(start + run_start as Address, &mut buffer[run_start..offset]);
+    }
+}
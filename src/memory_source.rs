@@ -0,0 +1,241 @@
+//! The [`MemorySource`] trait, which abstracts over the memory displayed by the [`crate::MemoryEditor`].
+use std::borrow::Cow;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+use crate::Address;
+
+/// Metadata describing a region of memory, see [`MemorySource::region`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryRegion {
+    /// The name of the region, shown when hovering over one of its addresses.
+    ///
+    /// Regions are looked up for every drawn address, so prefer a static name (e.g. `"VRAM".into()`), which doesn't
+    /// allocate.
+    pub name: Cow<'static, str>,
+    /// The addresses covered by this region.
+    pub range: Range<Address>,
+    /// Whether the addresses within this region can be written to.
+    pub writable: bool,
+    /// Whether the addresses within this region can be read without side effects, see [`MemorySource::peek`].
+    ///
+    /// If this is `false` the editor will display these addresses as
+    /// [`crate::option_data::MemoryEditorOptions::none_display_value`], unless the user explicitly reads them.
+    pub peekable: bool,
+}
+
+/// A source of memory which can be displayed, and optionally edited, by the [`crate::MemoryEditor`].
+///
+/// Only [`MemorySource::read`] is required, all other methods have default implementations which can be overridden
+/// when a faster (or more accurate) path is available.
+///
+/// The editor distinguishes between *peeking*, which is used for everything it renders and should never have side
+/// effects, and *reading*, which is only done when the user explicitly requests it through the context menu of an
+/// address. Sources where reads have side effects (e.g. registers which clear flags when read) should override
+/// [`MemorySource::peek`] and/or mark regions as not [`MemoryRegion::peekable`].
+///
+/// Implementations are provided for `[u8]` and `Vec<u8>`, and a pair of closures can be used through [`ClosureSource`].
+///
+/// ```no_run
+/// # use egui_memory_editor::{Address, MemoryEditor, MemorySource};
+/// # let ctx = egui::Context::default();
+/// struct Bus {
+///     ram: [u8; 0x2000],
+/// }
+///
+/// impl MemorySource for Bus {
+///     fn read(&mut self, address: Address) -> Option<u8> {
+///         self.ram.get(address as usize).copied()
+///     }
+///
+///     fn write(&mut self, address: Address, value: u8) {
+///         self.ram[address as usize] = value;
+///     }
+///
+///     fn is_writable(&self, address: Address) -> bool {
+///         address < self.ram.len() as Address
+///     }
+/// }
+///
+/// let mut bus = Bus { ram: [0; 0x2000] };
+/// let mut is_open = true;
+/// let mut memory_editor = MemoryEditor::new().with_address_range("RAM", 0..0x2000);
+///
+/// memory_editor.window_ui_with_source(&ctx, &mut is_open, &mut bus);
+/// ```
+pub trait MemorySource {
+    /// Read one `u8` value at the given `address`, as the hardware would.
+    ///
+    /// Can return `None` if the data at the specified address is not available for whatever reason. This will then be
+    /// rendered as `--` (See [`crate::option_data::MemoryEditorOptions::none_display_value`])
+    fn read(&mut self, address: Address) -> Option<u8>;
+
+    /// Read one `u8` value at the given `address` without triggering any side effects.
+    ///
+    /// The default implementation calls [`MemorySource::read`].
+    fn peek(&mut self, address: Address) -> Option<u8> {
+        self.read(address)
+    }
+
+    /// Peek `buffer.len()` consecutive values starting at `start` into `buffer`.
+    ///
+    /// The editor fetches all visible memory through this method once per frame, and reuses the result for the hex
+    /// cells, the ASCII sidebar and the data preview. It will never be called for addresses which aren't
+    /// [`MemorySource::is_peekable`].
+    ///
+    /// The default implementation calls [`MemorySource::peek`] for every address.
+    fn peek_range(&mut self, start: Address, buffer: &mut [Option<u8>]) {
+        for (i, value) in buffer.iter_mut().enumerate() {
+            *value = start.checked_add(i as Address).and_then(|address| self.peek(address));
+        }
+    }
+
+    /// Write `value` to the given `address`.
+    ///
+    /// This will only be called for addresses for which [`MemorySource::is_writable`] returns `true`.
+    /// The default implementation does nothing.
+    fn write(&mut self, _address: Address, _value: u8) {}
+
+    /// Whether the given `address` can be written to by the editor.
+    ///
+    /// The default implementation defers to the [`MemorySource::region`] of the address, and is read-only if there
+    /// is none.
+    fn is_writable(&self, address: Address) -> bool {
+        self.region(address).is_some_and(|region| region.writable)
+    }
+
+    /// Whether the given `address` can be peeked without side effects.
+    ///
+    /// The default implementation defers to the [`MemorySource::region`] of the address, and is peekable if there
+    /// is none.
+    fn is_peekable(&self, address: Address) -> bool {
+        self.region(address).map_or(true, |region| region.peekable)
+    }
+
+    /// Return the metadata of the region containing `address`, if there is any.
+    fn region(&self, _address: Address) -> Option<MemoryRegion> {
+        None
+    }
+}
+
+impl MemorySource for [u8] {
+    #[inline]
+    fn read(&mut self, address: Address) -> Option<u8> {
+        self.get(usize::try_from(address).ok()?).copied()
+    }
+
+    fn peek_range(&mut self, start: Address, buffer: &mut [Option<u8>]) {
+        let available = usize::try_from(start)
+            .ok()
+            .and_then(|start| self.get(start..))
+            .unwrap_or_default();
+
+        for (i, value) in buffer.iter_mut().enumerate() {
+            *value = available.get(i).copied();
+        }
+    }
+
+    #[inline]
+    fn write(&mut self, address: Address, value: u8) {
+        if let Some(byte) = usize::try_from(address).ok().and_then(|address| self.get_mut(address)) {
+            *byte = value;
+        }
+    }
+
+    #[inline]
+    fn is_writable(&self, address: Address) -> bool {
+        usize::try_from(address).is_ok_and(|address| address < self.len())
+    }
+}
+
+impl MemorySource for Vec<u8> {
+    #[inline]
+    fn read(&mut self, address: Address) -> Option<u8> {
+        self.as_mut_slice().read(address)
+    }
+
+    #[inline]
+    fn peek_range(&mut self, start: Address, buffer: &mut [Option<u8>]) {
+        self.as_mut_slice().peek_range(start, buffer)
+    }
+
+    #[inline]
+    fn write(&mut self, address: Address, value: u8) {
+        self.as_mut_slice().write(address, value)
+    }
+
+    #[inline]
+    fn is_writable(&self, address: Address) -> bool {
+        self.as_slice().is_writable(address)
+    }
+}
+
+/// A [`MemorySource`] which can be shared with a worker thread, see [`crate::MemoryEditor::set_search_source`].
+pub type SharedMemorySource = Arc<Mutex<dyn MemorySource + Send>>;
+
+/// The signature of the write function used by read-only [`ClosureSource`]s.
+pub type ReadOnlyWriteFn<T> = fn(&mut T, Address, u8);
+
+/// A [`MemorySource`] made out of a reference to some memory and a `read_fn`/`write_fn` closure pair.
+///
+/// This is what the closure based methods like [`crate::MemoryEditor::window_ui`] use internally.
+pub struct ClosureSource<'a, T: ?Sized, R, W> {
+    mem: &'a mut T,
+    read_fn: R,
+    write_fn: Option<W>,
+}
+
+impl<'a, T, R> ClosureSource<'a, T, R, ReadOnlyWriteFn<T>>
+where
+    T: ?Sized,
+    R: FnMut(&mut T, Address) -> Option<u8>,
+{
+    /// Create a read-only source, every address will report itself as not writable.
+    pub fn read_only(mem: &'a mut T, read_fn: R) -> Self {
+        ClosureSource {
+            mem,
+            read_fn,
+            write_fn: None,
+        }
+    }
+}
+
+impl<'a, T, R, W> ClosureSource<'a, T, R, W>
+where
+    T: ?Sized,
+    R: FnMut(&mut T, Address) -> Option<u8>,
+    W: FnMut(&mut T, Address, u8),
+{
+    /// Create a source which reads with `read_fn` and writes with `write_fn`.
+    pub fn new(mem: &'a mut T, read_fn: R, write_fn: W) -> Self {
+        ClosureSource {
+            mem,
+            read_fn,
+            write_fn: Some(write_fn),
+        }
+    }
+}
+
+impl<T, R, W> MemorySource for ClosureSource<'_, T, R, W>
+where
+    T: ?Sized,
+    R: FnMut(&mut T, Address) -> Option<u8>,
+    W: FnMut(&mut T, Address, u8),
+{
+    #[inline]
+    fn read(&mut self, address: Address) -> Option<u8> {
+        (self.read_fn)(self.mem, address)
+    }
+
+    #[inline]
+    fn write(&mut self, address: Address, value: u8) {
+        if let Some(write_fn) = self.write_fn.as_mut() {
+            write_fn(self.mem, address, value);
+        }
+    }
+
+    #[inline]
+    fn is_writable(&self, _address: Address) -> bool {
+        self.write_fn.is_some()
+    }
+}
@@ -1,5 +1,13 @@
+use crate::fill::FillDialog;
+use crate::memory_cache::MemoryCache;
+use crate::scanner::ValueScanner;
+use crate::search::{ReplaceAll, SearchRequest, SearchTask};
+use crate::selection::{Pane, Selection};
+use crate::snapshot::SnapshotDiff;
+use crate::utilities::AddressRangeExt;
 use crate::Address;
-use egui::{Color32, TextStyle};
+use egui::{Color32, Rect, Response, TextStyle, Ui};
+use std::collections::{BTreeMap, HashMap};
 use std::ops::Range;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -20,29 +28,418 @@ impl Endianness {
 pub enum DataFormatType {
     U8,
     U16,
+    U24,
     U32,
     U64,
+    U128,
     I8,
     I16,
+    I24,
     I32,
     I64,
+    I128,
+    /// IEEE 754 half precision float.
+    F16,
+    /// The `bfloat16` format, the upper half of an `f32`.
+    BF16,
     F32,
     F64,
+    /// Binary coded decimal with two digits per byte, of 1 to 16 bytes.
+    PackedBcd {
+        bytes: u8,
+    },
+    /// Binary coded decimal with one digit per byte in the lower nibble, of 1 to 16 bytes.
+    UnpackedBcd {
+        bytes: u8,
+    },
+    /// Signed 16 bit fixed point number, with the given amount of fractional bits.
+    Q16 {
+        fractional_bits: u8,
+    },
+    /// Signed 32 bit fixed point number, with the given amount of fractional bits.
+    Q32 {
+        fractional_bits: u8,
+    },
+    /// Unsigned variable length integer of up to 64 bits, as used by DWARF, WebAssembly and Protobuf.
+    Uleb128,
+    /// Signed variable length integer of up to 64 bits.
+    Sleb128,
 }
 
 impl DataFormatType {
     pub fn iter() -> impl Iterator<Item = DataFormatType> {
         use DataFormatType::*;
-        [U8, U16, U32, U64, I8, I16, I32, I64, F32, F64].into_iter()
+        [
+            U8,
+            U16,
+            U24,
+            U32,
+            U64,
+            U128,
+            I8,
+            I16,
+            I24,
+            I32,
+            I64,
+            I128,
+            F16,
+            BF16,
+            F32,
+            F64,
+            PackedBcd { bytes: 4 },
+            UnpackedBcd { bytes: 4 },
+            Q16 { fractional_bits: 8 },
+            Q32 { fractional_bits: 16 },
+            Uleb128,
+            Sleb128,
+        ]
+        .into_iter()
     }
 
+    /// The amount of bytes needed to decode a value, for variable length formats this is the maximum length.
     pub const fn bytes_to_read(&self) -> usize {
         use DataFormatType::*;
         match *self {
             U8 | I8 => 1,
-            U16 | I16 => 2,
-            U32 | I32 | F32 => 4,
+            U16 | I16 | F16 | BF16 | Q16 { .. } => 2,
+            U24 | I24 => 3,
+            U32 | I32 | F32 | Q32 { .. } => 4,
             U64 | I64 | F64 => 8,
+            U128 | I128 => 16,
+            // Up to 16 bytes, so that every value fits in a `u128`.
+            PackedBcd { bytes } | UnpackedBcd { bytes } => match bytes {
+                0 => 1,
+                1..=16 => bytes as usize,
+                _ => 16,
+            },
+            Uleb128 | Sleb128 => 10,
+        }
+    }
+
+    /// The amount of bytes the value at the start of `bytes` actually takes up, which is only shorter than
+    /// [`Self::bytes_to_read`] for variable length formats.
+    pub fn encoded_length(&self, bytes: &[u8]) -> usize {
+        match self {
+            DataFormatType::Uleb128 | DataFormatType::Sleb128 => bytes
+                .iter()
+                .position(|byte| byte & 0x80 == 0)
+                .map_or(bytes.len(), |last| last + 1),
+            _ => self.bytes_to_read().min(bytes.len()),
+        }
+    }
+
+    /// The maximum amount of characters of a value of this type, as shown in the hex area.
+    pub const fn max_display_length(&self) -> usize {
+        use DataFormatType::*;
+
+        match *self {
+            U8 => 3,
+            I8 => 4,
+            U16 => 5,
+            I16 => 6,
+            U24 | I24 => 8,
+            U32 => 10,
+            I32 => 11,
+            U64 | I64 | Uleb128 | Sleb128 => 20,
+            U128 => 39,
+            I128 => 40,
+            F16 | BF16 | F32 => 14,
+            F64 | Q16 { .. } | Q32 { .. } => 24,
+            PackedBcd { .. } => 2 * self.bytes_to_read(),
+            UnpackedBcd { .. } => self.bytes_to_read(),
+        }
+    }
+
+    /// Whether the [`Endianness`] affects how values are decoded.
+    pub const fn has_endianness(&self) -> bool {
+        !matches!(self, DataFormatType::Uleb128 | DataFormatType::Sleb128) && self.bytes_to_read() > 1
+    }
+
+    /// Whether `other` is the same type, ignoring its parameters.
+    pub fn is_same_type(&self, other: &DataFormatType) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    pub fn name(&self) -> &'static str {
+        use DataFormatType::*;
+        match self {
+            U8 => "U8",
+            U16 => "U16",
+            U24 => "U24",
+            U32 => "U32",
+            U64 => "U64",
+            U128 => "U128",
+            I8 => "I8",
+            I16 => "I16",
+            I24 => "I24",
+            I32 => "I32",
+            I64 => "I64",
+            I128 => "I128",
+            F16 => "F16",
+            BF16 => "BF16",
+            F32 => "F32",
+            F64 => "F64",
+            PackedBcd { .. } => "Packed BCD",
+            UnpackedBcd { .. } => "Unpacked BCD",
+            Q16 { .. } => "Q16",
+            Q32 { .. } => "Q32",
+            Uleb128 => "ULEB128",
+            Sleb128 => "SLEB128",
+        }
+    }
+}
+
+/// The formats in which selected memory can be copied to the clipboard.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CopyFormat {
+    /// Space separated hex values, e.g. `DE AD BE EF`.
+    HexSpaced,
+    /// Contiguous hex values, e.g. `DEADBEEF`.
+    HexContiguous,
+    /// An `xxd` style dump with addresses and ASCII, laid out like the rows in the editor.
+    HexDump,
+    /// A C array literal, e.g. `unsigned char data[4] = { 0xDE, 0xAD, 0xBE, 0xEF };`.
+    CArray,
+    /// A Rust array literal, e.g. `let data: [u8; 4] = [0xDE, 0xAD, 0xBE, 0xEF];`.
+    RustArray,
+    /// Standard Base64 with padding.
+    Base64,
+    /// The text as shown in the ASCII sidebar.
+    Ascii,
+}
+
+impl CopyFormat {
+    pub fn iter() -> impl Iterator<Item = CopyFormat> {
+        use CopyFormat::*;
+        [HexSpaced, HexContiguous, HexDump, CArray, RustArray, Base64, Ascii].into_iter()
+    }
+
+    /// A human readable name for this format.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            CopyFormat::HexSpaced => "Hex (spaced)",
+            CopyFormat::HexContiguous => "Hex (contiguous)",
+            CopyFormat::HexDump => "Hex dump",
+            CopyFormat::CArray => "C array",
+            CopyFormat::RustArray => "Rust array",
+            CopyFormat::Base64 => "Base64",
+            CopyFormat::Ascii => "ASCII text",
+        }
+    }
+}
+
+/// How the values in the hex area are displayed and typed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellRadix {
+    /// E.g. `AB`.
+    HexUpper,
+    /// E.g. `ab`.
+    HexLower,
+    Decimal,
+    /// Decimal, interpreting the value as two's complement.
+    SignedDecimal,
+    Octal,
+    Binary,
+}
+
+impl CellRadix {
+    pub fn iter() -> impl Iterator<Item = CellRadix> {
+        use CellRadix::*;
+        [HexUpper, HexLower, Decimal, SignedDecimal, Octal, Binary].into_iter()
+    }
+
+    /// A human readable name for this radix.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            CellRadix::HexUpper => "Hex",
+            CellRadix::HexLower => "Hex (lowercase)",
+            CellRadix::Decimal => "Decimal",
+            CellRadix::SignedDecimal => "Signed decimal",
+            CellRadix::Octal => "Octal",
+            CellRadix::Binary => "Binary",
+        }
+    }
+
+    /// The amount of characters needed to display any value of `bytes` bytes, up to 8.
+    pub fn max_length(&self, bytes: usize) -> usize {
+        let bits = 8 * bytes as u32;
+        let max = u64::MAX >> (64 - bits);
+
+        match self {
+            CellRadix::HexUpper | CellRadix::HexLower => 2 * bytes,
+            CellRadix::Decimal => max.to_string().len(),
+            // The minimum value, including the sign.
+            CellRadix::SignedDecimal => (max / 2).to_string().len() + 1,
+            CellRadix::Octal => bits.div_ceil(3) as usize,
+            CellRadix::Binary => bits as usize,
+        }
+    }
+
+    /// Format the `value` of `bytes` bytes, padded to [`Self::max_length`].
+    pub fn format(&self, value: u64, bytes: usize) -> String {
+        let width = self.max_length(bytes);
+
+        match self {
+            CellRadix::HexUpper => format!("{:01$X}", value, width),
+            CellRadix::HexLower => format!("{:01$x}", value, width),
+            CellRadix::Decimal => format!("{:>1$}", value, width),
+            CellRadix::SignedDecimal => {
+                let unused_bits = 64 - 8 * bytes as u32;
+                format!("{:>1$}", ((value << unused_bits) as i64) >> unused_bits, width)
+            }
+            CellRadix::Octal => format!("{:01$o}", value, width),
+            CellRadix::Binary => format!("{:01$b}", value, width),
+        }
+    }
+
+    /// Whether `c` can be typed when editing a value, a `-` is only valid at the start of a signed value.
+    pub fn is_digit(&self, c: char, index: usize) -> bool {
+        match self {
+            CellRadix::HexUpper | CellRadix::HexLower => c.is_ascii_hexdigit(),
+            CellRadix::Decimal => c.is_ascii_digit(),
+            CellRadix::SignedDecimal => c.is_ascii_digit() || (c == '-' && index == 0),
+            CellRadix::Octal => matches!(c, '0'..='7'),
+            CellRadix::Binary => matches!(c, '0' | '1'),
+        }
+    }
+
+    /// Whether `text` has as many digits as a value of `bytes` bytes can have, so editing can move on to the next value.
+    pub fn is_complete(&self, text: &str, bytes: usize) -> bool {
+        let max_length = match self {
+            // Positive values don't have a sign.
+            CellRadix::SignedDecimal if !text.starts_with('-') => self.max_length(bytes) - 1,
+            _ => self.max_length(bytes),
+        };
+
+        text.chars().count() >= max_length
+    }
+
+    /// Parse a typed value of `bytes` bytes, returns `None` if it's not valid or doesn't fit.
+    pub fn parse(&self, text: &str, bytes: usize) -> Option<u64> {
+        let bits = 8 * bytes as u32;
+        let max = u64::MAX >> (64 - bits);
+
+        let value = match self {
+            CellRadix::HexUpper | CellRadix::HexLower => u64::from_str_radix(text, 16).ok()?,
+            CellRadix::Decimal => text.parse().ok()?,
+            CellRadix::SignedDecimal => {
+                let value: i64 = text.parse().ok()?;
+                let min = -((max / 2) as i64) - 1;
+                // Allow the unsigned range as well, as that's unambiguous.
+                if value < min || (value > 0 && value as u64 > max) {
+                    return None;
+                }
+                value as u64 & max
+            }
+            CellRadix::Octal => u64::from_str_radix(text, 8).ok()?,
+            CellRadix::Binary => u64::from_str_radix(text, 2).ok()?,
+        };
+
+        (value <= max).then_some(value)
+    }
+}
+
+/// How the query in the search bar is interpreted.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SearchKind {
+    /// Hex bytes, where `??` matches any byte, e.g. `DE AD ?? EF`.
+    Hex,
+    /// A string in the selected [`TextEncoding`].
+    Text,
+    /// A number in the selected [`DataFormatType`] and [`Endianness`].
+    Value,
+}
+
+impl SearchKind {
+    pub fn iter() -> impl Iterator<Item = SearchKind> {
+        [SearchKind::Hex, SearchKind::Text, SearchKind::Value].into_iter()
+    }
+}
+
+/// The character encodings of the text sidebar, and in which text can be searched for.
+///
+/// See [`crate::encoding::CharacterDecoder`] for the conversion from and to bytes.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextEncoding {
+    /// 7-bit ASCII.
+    Ascii,
+    /// ISO 8859-1, where every byte is the Unicode code point of the same value.
+    Latin1,
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// EBCDIC code page 037.
+    Ebcdic,
+    /// Only available with the `shift-jis` feature.
+    #[cfg(feature = "shift-jis")]
+    ShiftJis,
+}
+
+impl TextEncoding {
+    pub fn iter() -> impl Iterator<Item = TextEncoding> {
+        use TextEncoding::*;
+        [
+            Ascii,
+            Latin1,
+            Utf8,
+            Utf16Le,
+            Utf16Be,
+            Ebcdic,
+            #[cfg(feature = "shift-jis")]
+            ShiftJis,
+        ]
+        .into_iter()
+    }
+
+    /// A human readable name for this encoding.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            TextEncoding::Ascii => "ASCII",
+            TextEncoding::Latin1 => "Latin-1",
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf16Le => "UTF-16 LE",
+            TextEncoding::Utf16Be => "UTF-16 BE",
+            TextEncoding::Ebcdic => "EBCDIC",
+            #[cfg(feature = "shift-jis")]
+            TextEncoding::ShiftJis => "Shift-JIS",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SearchOptions {
+    pub kind: SearchKind,
+    pub text_encoding: TextEncoding,
+    /// Whether text is encoded with the character table of the current address range, if it has one, instead of the
+    /// `text_encoding`.
+    pub use_character_table: bool,
+    /// Whether text searches distinguish between upper and lower case, only applies to ASCII letters.
+    pub case_sensitive: bool,
+    /// The number type used when searching for a [`SearchKind::Value`].
+    pub value_format: DataPreviewOptions,
+    /// Whether a replacement of a different length than the matches is padded with `0x00`, or truncated, to fit.
+    /// Otherwise such replacements are refused, as the editor can't insert or remove bytes.
+    pub fit_replacement: bool,
+    /// Whether "Replace all" only replaces the matches inside the current selection.
+    pub replace_in_selection: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            kind: SearchKind::Hex,
+            text_encoding: TextEncoding::Ascii,
+            use_character_table: false,
+            case_sensitive: true,
+            value_format: Default::default(),
+            fit_replacement: false,
+            replace_in_selection: false,
         }
     }
 }
@@ -72,17 +469,38 @@ pub struct MemoryEditorOptions {
     pub show_ascii: bool,
     /// Whether `0x00` values in the main UI should use the [`MemoryEditorOptions::zero_colour`].
     pub show_zero_colour: bool,
-    /// The value to display if the `read_fn` ever returns [Option::None].
+    /// The value to display if the `read_fn` ever returns [Option::None], or if the address can't be peeked.
     pub none_display_value: String,
     /// Whether the options header is collapsed by default or not.
     /// Default is `false`.
     pub is_options_collapsed: bool,
     /// The options which determine how to interpret selected data, concerning endianness and number type.
     pub data_preview: DataPreviewOptions,
-    /// The amount of columns for the main UI, this amount directly impacts the possible size of your address space.
-    ///
-    /// At the moment, you'll at most be able to display the range: `0..2^(24 + log_2(column_count))`.
+    /// The options of the search bar, concerning what the query is interpreted as.
+    pub search: SearchOptions,
+    /// The amount of columns for the main UI.
     pub column_count: usize,
+    /// How the values in the hex area are displayed and typed.
+    /// Default is [`CellRadix::HexUpper`].
+    pub cell_radix: CellRadix,
+    /// The amount of bytes shown as a single value in the hex area, either 1, 2, 4 or 8.
+    /// Rows are made longer if the [`MemoryEditorOptions::column_count`] isn't a multiple of this.
+    /// Default is `1`.
+    pub word_size: usize,
+    /// The byte order of the values in the hex area, when the [`MemoryEditorOptions::word_size`] is more than `1`.
+    /// Default is [`Endianness::Little`].
+    pub word_endianness: Endianness,
+    /// When set, every cell in the hex area shows a decoded value of this number type instead of a byte or word, and
+    /// [`MemoryEditorOptions::column_count`] is the amount of values per row. Values are typed in decimal.
+    /// Variable length types, like LEB128, take up their maximum length.
+    /// Default is `None`.
+    pub typed_grid: Option<DataPreviewOptions>,
+    /// The character encoding of the text sidebar.
+    /// Default is [`TextEncoding::Ascii`].
+    pub text_encoding: TextEncoding,
+    /// The name of the character table used by the text sidebar of an address range, by the name of that range.
+    /// Ranges without a table use the `text_encoding`, see [`crate::MemoryEditor::with_character_table`].
+    pub range_character_tables: BTreeMap<String, String>,
     /// Whether column size can be modified
     /// Default is `true`.
     pub is_resizable_column: bool,
@@ -93,6 +511,36 @@ pub struct MemoryEditorOptions {
     /// The highlight colour for both the main UI and the ASCII sidebar.
     /// This will be enabled when you right-click an address, or when using the `goto address` function in the UI.
     pub highlight_text_colour: Color32,
+    /// Whether values which changed since the previous frame are drawn in the
+    /// [`MemoryEditorOptions::change_highlight_colour`], fading back over the
+    /// [`MemoryEditorOptions::change_highlight_duration`].
+    /// Default is `true`.
+    pub highlight_changes: bool,
+    /// The colour of values which just changed.
+    pub change_highlight_colour: Color32,
+    /// The amount of seconds it takes for the highlight of a changed value to fade out.
+    /// Default is `1.0`.
+    pub change_highlight_duration: f32,
+    /// The background colour of values which differ from the snapshot they're being compared with, or from the other
+    /// memory in a side-by-side comparison.
+    pub diff_colour: Color32,
+    /// The format used when copying the selection with `Ctrl+C`.
+    /// Other formats are available in the context menu.
+    /// Default is [`CopyFormat::HexSpaced`]
+    pub copy_format: CopyFormat,
+    /// Pastes of more bytes than this will show a preview, and have to be confirmed before they're written.
+    /// Default is `256`.
+    pub paste_confirmation_threshold: usize,
+    /// The amount of bytes searched per frame, lower this if reading from your [`crate::MemorySource`] is slow.
+    /// Searches on a [`crate::memory_source::SharedMemorySource`] run on a worker thread and ignore this.
+    /// Default is `1 MiB`.
+    pub search_bytes_per_frame: usize,
+    /// The size in bytes of the pointer shown in the data inspector.
+    /// Default is the pointer size of the host.
+    pub inspector_pointer_size: usize,
+    /// The maximum amount of operations which can be undone.
+    /// Default is `100`.
+    pub undo_history_length: usize,
     /// The [`egui::TextStyle`] for the main UI, indicating the values.
     /// Default is [`egui::TextStyle::Monospace`]
     pub memory_editor_text_style: TextStyle,
@@ -110,6 +558,7 @@ impl Default for MemoryEditorOptions {
     fn default() -> Self {
         MemoryEditorOptions {
             data_preview: Default::default(),
+            search: Default::default(),
             show_ascii: true,
             show_zero_colour: true,
             none_display_value: "--".to_string(),
@@ -117,8 +566,23 @@ impl Default for MemoryEditorOptions {
             zero_colour: Color32::from_gray(80),
             is_resizable_column: true,
             column_count: 16,
+            cell_radix: CellRadix::HexUpper,
+            word_size: 1,
+            word_endianness: Endianness::Little,
+            typed_grid: None,
+            text_encoding: TextEncoding::Ascii,
+            range_character_tables: BTreeMap::new(),
             address_text_colour: Color32::from_rgb(125, 0, 125),
             highlight_text_colour: Color32::from_rgb(0, 140, 140),
+            highlight_changes: true,
+            change_highlight_colour: Color32::from_rgb(230, 60, 60),
+            change_highlight_duration: 1.0,
+            diff_colour: Color32::from_rgba_unmultiplied(230, 60, 60, 70),
+            copy_format: CopyFormat::HexSpaced,
+            paste_confirmation_threshold: 256,
+            search_bytes_per_frame: 1024 * 1024,
+            inspector_pointer_size: std::mem::size_of::<usize>(),
+            undo_history_length: 100,
             memory_editor_text_style: TextStyle::Monospace,
             memory_editor_address_text_style: TextStyle::Monospace,
             memory_editor_ascii_text_style: TextStyle::Monospace,
@@ -127,12 +591,40 @@ impl Default for MemoryEditorOptions {
     }
 }
 
+impl MemoryEditorOptions {
+    /// The amount of bytes shown by a single cell in the hex area.
+    pub fn cell_width(&self) -> usize {
+        match self.typed_grid {
+            Some(typed_grid) => typed_grid.selected_data_format.bytes_to_read(),
+            None => self.word_size.clamp(1, 8),
+        }
+    }
+
+    /// The amount of bytes shown in a single row.
+    pub fn row_length(&self) -> usize {
+        match self.typed_grid {
+            Some(_) => self.column_count.max(1) * self.cell_width(),
+            None => self.column_count.max(1).next_multiple_of(self.cell_width()),
+        }
+    }
+}
+
+/// Pasted bytes which still have to be confirmed by the user.
+#[derive(Debug, Clone)]
+pub(crate) struct PendingPaste {
+    pub address: Address,
+    pub bytes: Vec<u8>,
+}
+
 /// Some extra, non-serializable state for between frames.
 #[derive(Debug, Default, Clone)]
 pub(crate) struct BetweenFrameData {
     /// Used to ensure we can resize the window in height, but not in width.
     pub previous_frame_editor_width: f32,
     pub previous_frame_text_edit_size: f32,
+    pub previous_frame_ascii_text_edit_size: f32,
+    /// The height of a single row of the main UI, including spacing.
+    pub previous_frame_row_height: f32,
     /// The address a user clicked on in the UI in the previous frame, used for DataPreview
     pub selected_edit_address: Option<Address>,
     pub selected_edit_address_string: String,
@@ -141,12 +633,76 @@ pub(crate) struct BetweenFrameData {
     pub memory_range_combo_box_enabled: bool,
 
     pub selected_highlight_address: Option<Address>,
+    /// The range of addresses selected by dragging, Shift+clicking, or Shift+arrow keys.
+    /// The highlight address always follows the cursor of the selection.
+    pub selection: Option<Selection>,
+    /// Whether the user is currently dragging out a selection.
+    pub is_selecting: bool,
+    /// The area in which the selection was last made, determines how pasted text is interpreted and in which area
+    /// the edit cursor is shown.
+    pub active_pane: Pane,
+    /// Whether keyboard navigation applies to the selection, set when clicking inside the editor.
+    pub has_keyboard_focus: bool,
+    /// The area the rows were drawn in, used to check whether a click happened outside of the editor.
+    pub previous_frame_grid_rect: Option<Rect>,
     /// Whether to show additional highlights around items after the current selected item when they'd be part
     /// of the value in the data preview section.
     pub show_additional_highlights: bool,
 
+    /// A paste which is awaiting confirmation, as it exceeded [`MemoryEditorOptions::paste_confirmation_threshold`].
+    pub pending_paste: Option<PendingPaste>,
+    /// A copy of the given range to the clipboard, requested through the context menu or `Ctrl+C`.
+    pub pending_copy: Option<(CopyFormat, Range<Address>)>,
+    /// Why the last copy couldn't be performed.
+    pub copy_message: Option<String>,
+    /// Why the last undo through `Ctrl+Z` couldn't be performed.
+    pub history_message: Option<String>,
+    /// The "Fill selection" dialog, shown while it has a range to fill.
+    pub fill: FillDialog,
+    /// The address and result of the last explicit read requested through the context menu.
+    /// Memory is otherwise only ever peeked.
+    pub last_real_read: Option<(Address, Option<u8>)>,
+
+    /// The value typed into the data preview, while it hasn't been written yet.
+    pub data_preview_edit: Option<String>,
+    /// Why the value typed into the data preview couldn't be written.
+    pub data_preview_error: Option<String>,
+
     pub goto_address_string: String,
-    pub goto_address_line: Option<usize>,
+    pub goto_address_line: Option<u64>,
+
+    pub search_query: String,
+    pub replace_query: String,
+    /// The replacement of all matches which is performed once the search in progress has finished.
+    pub pending_replace_all: Option<ReplaceAll>,
+    /// The result of the last search, or why it couldn't be performed.
+    pub search_message: Option<String>,
+    /// A search requested through the search bar, started before the rows are drawn.
+    pub pending_search: Option<SearchRequest>,
+    /// The search which is currently in progress.
+    pub search_task: Option<SearchTask>,
+    /// The matches of the last search for all matches, and the length of those matches.
+    pub search_results: Vec<Address>,
+    pub search_results_length: usize,
+    pub value_scanner: ValueScanner,
+
+    /// The name typed in for the next snapshot.
+    pub snapshot_name: String,
+    /// Why the last snapshot couldn't be taken.
+    pub snapshot_message: Option<String>,
+    /// The comparison of the current memory with a snapshot, while one is being compared.
+    pub snapshot_diff: Option<SnapshotDiff>,
+    /// A copy of the given range from the left to the right memory of a side-by-side comparison.
+    pub pending_copy_left_to_right: Option<Range<Address>>,
+
+    /// The first visible line for every address range.
+    pub top_lines: HashMap<String, u64>,
+    /// An address which should be scrolled into view in the next frame.
+    pub scroll_to_address: Option<Address>,
+    /// Scroll distance which didn't add up to a full line yet.
+    pub scroll_remainder: f32,
+    /// Where on the scrollbar handle the user grabbed it, while dragging.
+    pub scrollbar_grab_offset: Option<f32>,
 }
 
 impl BetweenFrameData {
@@ -155,34 +711,143 @@ impl BetweenFrameData {
         self.selected_highlight_address = if matches!(self.selected_highlight_address, Some(current) if current == new_address)
         {
             self.goto_address_string.clear();
+            self.selection = None;
             None
         } else {
             self.goto_address_string = format!("{:X}", new_address);
+            self.selection = Some(Selection::new(new_address));
             Some(new_address)
         };
     }
 
+    /// Replace the current selection, the highlight will move to its cursor.
+    pub fn select(&mut self, selection: Selection) {
+        self.selection = Some(selection);
+        self.selected_highlight_address = Some(selection.cursor);
+        self.goto_address_string = format!("{:X}", selection.cursor);
+    }
+
+    /// Move the cursor of the current selection to `cursor`, or start a new selection if there is none.
+    pub fn extend_selection(&mut self, cursor: Address) {
+        let anchor = self.selection.map_or(cursor, |selection| selection.anchor);
+        self.select(Selection { anchor, cursor });
+    }
+
+    /// Extend the current selection so that it covers all of `cell`, as well as the entire cell of its anchor.
+    /// All cells are expected to be as wide as `cell`, and aligned to it.
+    fn extend_selection_to_cell(&mut self, cell: Range<Address>) {
+        let width = cell.length().max(1);
+        let anchor = self.selection.map_or(cell.start, |selection| selection.anchor);
+        let anchor_start = if anchor >= cell.start {
+            anchor - (anchor - cell.start) % width
+        } else {
+            anchor - (width - (cell.start - anchor) % width) % width
+        };
+
+        if cell.start >= anchor_start {
+            self.select(Selection {
+                anchor: anchor_start,
+                cursor: cell.end - 1,
+            });
+        } else {
+            self.select(Selection {
+                anchor: anchor_start + width - 1,
+                cursor: cell.start,
+            });
+        }
+    }
+
+    /// Update the selection based on the pointer interaction with `cell`, in either the hex or ASCII area.
+    /// A cell is a single byte, unless the hex area shows words.
+    pub fn handle_selection_input(&mut self, ui: &Ui, response: &Response, cell: Range<Address>, pane: Pane) {
+        let (pressed, down, shift) =
+            ui.input(|i| (i.pointer.primary_pressed(), i.pointer.primary_down(), i.modifiers.shift));
+        // Can't use `response.hovered()`, as egui doesn't hover other widgets while one is being dragged.
+        let contains_pointer = ui.rect_contains_pointer(response.rect);
+
+        if pressed && contains_pointer {
+            self.has_keyboard_focus = true;
+            self.is_selecting = true;
+            self.active_pane = pane;
+
+            if shift {
+                self.extend_selection_to_cell(cell);
+            } else {
+                self.select(Selection {
+                    anchor: cell.start,
+                    cursor: cell.end - 1,
+                });
+            }
+        } else if self.is_selecting && down && contains_pointer {
+            if self
+                .selection
+                .map_or(true, |selection| !cell.contains(&selection.cursor))
+            {
+                self.extend_selection_to_cell(cell);
+            }
+        } else if !down {
+            self.is_selecting = false;
+        }
+    }
+
+    /// Whether `address` is part of a selection of more than one address.
+    #[inline]
+    pub fn is_selected(&self, address: Address) -> bool {
+        self.selection
+            .is_some_and(|selection| selection.is_multi_byte() && selection.contains(address))
+    }
+
     pub fn set_selected_edit_address(&mut self, new_address: Option<Address>, address_space: &Range<Address>) {
         self.selected_edit_address_string.clear();
         if matches!(new_address, Some(address) if address_space.contains(&address)) {
-            self.set_highlight_address(new_address.unwrap());
+            self.select(Selection::new(new_address.unwrap()));
             self.selected_edit_address_request_focus = true;
+            self.scroll_to_address = new_address;
             self.selected_edit_address = new_address;
         } else {
             self.selected_edit_address = None;
         }
     }
 
+    /// Consume the mouse wheel input of this frame and apply it to `top_line` in whole lines.
+    pub fn apply_scroll_delta(&mut self, ui: &Ui, top_line: u64, row_height: f32) -> u64 {
+        let delta = ui.ctx().input_mut(|i| std::mem::take(&mut i.smooth_scroll_delta.y));
+        self.scroll_remainder += delta;
+
+        let lines = (self.scroll_remainder / row_height).trunc();
+        self.scroll_remainder -= lines * row_height;
+
+        if lines > 0.0 {
+            top_line.saturating_sub(lines as u64)
+        } else {
+            top_line.saturating_add(-lines as u64)
+        }
+    }
+
     #[inline]
     pub fn should_highlight(&self, address: Address) -> bool {
-        self.selected_highlight_address.map_or(false, |addr| addr == address)
-            || self.selected_edit_address.map_or(false, |addr| addr == address)
+        self.selected_highlight_address == Some(address) || self.selected_edit_address == Some(address)
     }
 
-    pub fn should_subtle_highlight(&self, address: Address, data_format: DataFormatType) -> bool {
-        self.show_additional_highlights
-            && self.selected_highlight_address.map_or(false, |addr| {
-                (addr..addr + data_format.bytes_to_read()).contains(&address)
-            })
+    /// Whether `address` is part of the value shown in the data preview. The length of variable length values is
+    /// looked up in the `memory_cache`.
+    pub fn should_subtle_highlight(
+        &self,
+        address: Address,
+        data_format: DataFormatType,
+        memory_cache: &MemoryCache,
+    ) -> bool {
+        if !self.show_additional_highlights {
+            return false;
+        }
+
+        self.selected_highlight_address.is_some_and(|addr| {
+            let bytes: Vec<u8> = (addr..addr.saturating_add(data_format.bytes_to_read() as Address))
+                .map_while(|a| memory_cache.get(a))
+                .collect();
+            let length = data_format.encoded_length(&bytes).max(1);
+
+            (addr..addr.saturating_add(length as Address)).contains(&address)
+        })
     }
 }
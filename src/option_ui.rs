@@ -1,18 +1,56 @@
 use std::ops::Range;
 
-use egui::Ui;
+use egui::{Response, Ui};
+
+use crate::history::EditHistory;
+use crate::memory_cache::MemoryCache;
+use crate::option_data::{CellRadix, DataFormatType, DataPreviewOptions, Endianness, TextEncoding};
+use crate::selection::Selection;
+use crate::{Address, MemoryEditor, MemorySource};
+
+/// Draw a combo box for selecting a [`DataFormatType`], followed by the parameters of the selected type, if it has
+/// any. Returns the response of the combo box.
+pub(crate) fn data_format_combo(ui: &mut Ui, combo: egui::ComboBox, format: &mut DataFormatType) -> Response {
+    let response = combo
+        .selected_text(format.name())
+        .show_ui(ui, |ui| {
+            for candidate in DataFormatType::iter() {
+                // Keep the parameters if the same type is selected again.
+                let is_selected = format.is_same_type(&candidate);
+                if ui.selectable_label(is_selected, candidate.name()).clicked() && !is_selected {
+                    *format = candidate;
+                }
+            }
+        })
+        .response;
+
+    match format {
+        DataFormatType::PackedBcd { bytes } | DataFormatType::UnpackedBcd { bytes } => {
+            ui.add(egui::DragValue::new(bytes).range(1..=16).suffix(" bytes"));
+        }
+        DataFormatType::Q16 { fractional_bits } => {
+            ui.add(
+                egui::DragValue::new(fractional_bits)
+                    .range(0..=16)
+                    .prefix("Fraction bits: "),
+            );
+        }
+        DataFormatType::Q32 { fractional_bits } => {
+            ui.add(
+                egui::DragValue::new(fractional_bits)
+                    .range(0..=32)
+                    .prefix("Fraction bits: "),
+            );
+        }
+        _ => {}
+    }
 
-use crate::option_data::{DataFormatType, DataPreviewOptions, Endianness};
-use crate::{Address, MemoryEditor};
+    response
+}
 
 impl MemoryEditor {
     /// Draw the `Options` collapsing header with the main options and data preview hidden underneath.
-    pub(crate) fn draw_options_area<T: ?Sized>(
-        &mut self,
-        ui: &mut Ui,
-        mem: &mut T,
-        read: &mut impl FnMut(&mut T, Address) -> Option<u8>,
-    ) {
+    pub(crate) fn draw_options_area<S: MemorySource + ?Sized>(&mut self, ui: &mut Ui, source: &mut S) {
         let current_address_range = self
             .address_ranges
             .get(&self.options.selected_address_range)
@@ -24,7 +62,15 @@ impl MemoryEditor {
             .show(ui, |ui| {
                 self.draw_main_options(ui, &current_address_range);
 
-                self.draw_data_preview(ui, &current_address_range, mem, read);
+                self.draw_search_options(ui, &current_address_range);
+
+                self.draw_value_scanner(ui, &current_address_range, source);
+
+                self.draw_snapshots(ui, &current_address_range, source);
+
+                self.draw_data_preview(ui, &current_address_range, source);
+
+                self.draw_data_inspector(ui, &current_address_range, source);
             });
     }
 
@@ -115,8 +161,9 @@ impl MemoryEditor {
 
                 self.frame_data.goto_address_line = address
                     .and_then(|addr| addr.checked_sub(current_address_range.start))
-                    .map(|addr| addr / self.options.column_count);
+                    .map(|addr| addr / self.options.row_length() as Address);
                 self.frame_data.selected_highlight_address = address;
+                self.frame_data.selection = address.map(Selection::new);
 
                 response.surrender_focus();
             }
@@ -132,18 +179,142 @@ impl MemoryEditor {
                 if *show_ascii_sidebar { "Disable" } else { "Enable" }
             ));
 
+            let text_encoding = &mut self.options.text_encoding;
+            let range_tables = &mut self.options.range_character_tables;
+            let range_name = &self.options.selected_address_range;
+            let table_name = range_tables
+                .get(range_name)
+                .filter(|name| self.character_tables.contains_key(*name))
+                .cloned();
+
+            egui::ComboBox::from_id_salt("TextEncodingCombo")
+                .selected_text(table_name.as_deref().unwrap_or(text_encoding.name()))
+                .show_ui(ui, |ui| {
+                    for encoding in TextEncoding::iter() {
+                        let is_selected = table_name.is_none() && *text_encoding == encoding;
+                        if ui.selectable_label(is_selected, encoding.name()).clicked() {
+                            *text_encoding = encoding;
+                            range_tables.remove(range_name);
+                        }
+                    }
+
+                    for name in self.character_tables.keys() {
+                        if ui.selectable_label(table_name.as_ref() == Some(name), name).clicked() {
+                            range_tables.insert(range_name.clone(), name.clone());
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(
+                    "Select the character encoding of the text sidebar, a character table only applies to the current \
+                    region",
+                );
+
             ui.checkbox(show_zero_colour, "Custom zero colour")
                 .on_hover_text("If enabled memory values of '0x00' will be coloured differently");
+
+            ui.horizontal(|ui| {
+                let options = &mut self.options;
+                ui.checkbox(&mut options.highlight_changes, "Highlight changes")
+                    .on_hover_text("If enabled memory values which change are coloured, fading out over time");
+
+                if options.highlight_changes {
+                    ui.add(
+                        egui::DragValue::new(&mut options.change_highlight_duration)
+                            .range(0.1..=10.0)
+                            .speed(0.05)
+                            .suffix(" s"),
+                    )
+                    .on_hover_text("How long it takes for the colour of a changed value to fade out");
+                }
+            });
+
+            ui.end_row();
+
+            // Cell display
+            let mut is_typed = self.options.typed_grid.is_some();
+            if ui
+                .checkbox(&mut is_typed, "Typed values")
+                .on_hover_text("Show every cell as a number of the selected type, instead of as bytes")
+                .changed()
+            {
+                // Start out with the type of the data preview, as that's likely what the user is looking at.
+                self.options.typed_grid = is_typed.then_some(self.options.data_preview);
+            }
+
+            if let Some(typed_grid) = &mut self.options.typed_grid {
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("TypedGridEndiannessCombo")
+                        .selected_text(format!("{:?}", typed_grid.selected_endianness))
+                        .show_ui(ui, |ui| {
+                            for endian in Endianness::iter() {
+                                ui.selectable_value(
+                                    &mut typed_grid.selected_endianness,
+                                    endian,
+                                    format!("{:?}", endian),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text("Select the endianness of the values");
+
+                    data_format_combo(
+                        ui,
+                        egui::ComboBox::from_id_salt("TypedGridFormatCombo"),
+                        &mut typed_grid.selected_data_format,
+                    )
+                    .on_hover_text(
+                        "Select the number type of the values, the columns are the amount of values per row",
+                    );
+                });
+                return;
+            }
+
+            let cell_radix = &mut self.options.cell_radix;
+            egui::ComboBox::from_id_salt("CellRadixCombo")
+                .selected_text(cell_radix.name())
+                .show_ui(ui, |ui| {
+                    for radix in CellRadix::iter() {
+                        ui.selectable_value(cell_radix, radix, radix.name());
+                    }
+                })
+                .response
+                .on_hover_text("Select how the values are displayed and typed");
+
+            ui.horizontal(|ui| {
+                let word_size = &mut self.options.word_size;
+                egui::ComboBox::from_id_salt("WordSizeCombo")
+                    .selected_text(format!("Group: {} B", word_size))
+                    .show_ui(ui, |ui| {
+                        for size in [1, 2, 4, 8] {
+                            ui.selectable_value(word_size, size, format!("{} bytes", size));
+                        }
+                    })
+                    .response
+                    .on_hover_text("Show and edit the memory as words of multiple bytes");
+
+                if *word_size > 1 {
+                    let word_endianness = &mut self.options.word_endianness;
+                    egui::ComboBox::from_id_salt("WordEndiannessCombo")
+                        .selected_text(format!("{:?}", word_endianness))
+                        .show_ui(ui, |ui| {
+                            for endian in Endianness::iter() {
+                                ui.selectable_value(word_endianness, endian, format!("{:?}", endian));
+                            }
+                        })
+                        .response
+                        .on_hover_text("Select the byte order of the words");
+                }
+            });
         });
     }
 
     /// Draws the data preview underneath a collapsing header.
-    fn draw_data_preview<T: ?Sized>(
+    fn draw_data_preview<S: MemorySource + ?Sized>(
         &mut self,
         ui: &mut Ui,
         current_address_range: &Range<Address>,
-        mem: &mut T,
-        read: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        source: &mut S,
     ) {
         let response = egui::CollapsingHeader::new("⛃ Data Preview")
             .default_open(false)
@@ -165,19 +336,12 @@ impl MemoryEditor {
                         .response
                         .on_hover_text("Select the endianness of the data");
 
-                    egui::ComboBox::from_label("Format")
-                        .selected_text(format!("{:?}", data_preview_options.selected_data_format))
-                        .show_ui(ui, |ui| {
-                            for format in DataFormatType::iter() {
-                                ui.selectable_value(
-                                    &mut data_preview_options.selected_data_format,
-                                    format,
-                                    format!("{:?}", format),
-                                );
-                            }
-                        })
-                        .response
-                        .on_hover_text("Select the number type for data interpretation");
+                    data_format_combo(
+                        ui,
+                        egui::ComboBox::from_label("Format"),
+                        &mut data_preview_options.selected_data_format,
+                    )
+                    .on_hover_text("Select the number type for data interpretation");
 
                     ui.end_row();
 
@@ -185,11 +349,68 @@ impl MemoryEditor {
                     let hover_text = "Right click a value in the UI to select it, right click again to unselect";
 
                     if let Some(address) = self.frame_data.selected_highlight_address {
-                        let value =
-                            Self::read_mem_value(mem, read, address, *data_preview_options, current_address_range);
+                        let data_preview = *data_preview_options;
+                        let none_display_value = self.options.none_display_value.clone();
+                        let value = Self::read_mem_value(
+                            &mut self.memory_cache,
+                            source,
+                            address,
+                            data_preview,
+                            current_address_range,
+                        );
                         ui.label(format!("Value at {:#X} (decimal): ", address))
                             .on_hover_text(hover_text);
-                        ui.label(value);
+
+                        // Show the live value, unless the user is typing a new one. Values which couldn't be read
+                        // leave the input empty.
+                        let frame_data = &mut self.frame_data;
+                        let mut text = frame_data.data_preview_edit.clone().or(value).unwrap_or_default();
+                        let width = data_preview.selected_data_format.bytes_to_read();
+                        let is_writable =
+                            (address..address.saturating_add(width as Address)).all(|a| source.is_writable(a));
+                        let response = ui.add(
+                            egui::TextEdit::singleline(&mut text)
+                                .hint_text(none_display_value)
+                                .interactive(is_writable),
+                        );
+
+                        if response.changed() {
+                            frame_data.data_preview_edit = Some(text);
+                        }
+
+                        if response.lost_focus() {
+                            let submitted = ui.input(|i| i.key_pressed(egui::Key::Enter));
+                            let edit = frame_data.data_preview_edit.take();
+
+                            match edit.filter(|_| submitted) {
+                                Some(edit) => {
+                                    match Self::write_mem_value(
+                                        &mut self.history,
+                                        &mut self.memory_cache,
+                                        source,
+                                        address,
+                                        data_preview,
+                                        current_address_range,
+                                        &edit,
+                                    ) {
+                                        Ok(()) => frame_data.data_preview_error = None,
+                                        Err(message) => {
+                                            // Keep the input around, so the user can correct it.
+                                            frame_data.data_preview_edit = Some(edit);
+                                            frame_data.data_preview_error = Some(message);
+                                            response.request_focus();
+                                        }
+                                    }
+                                }
+                                None => frame_data.data_preview_error = None,
+                            }
+                        }
+
+                        if let Some(message) = &frame_data.data_preview_error {
+                            ui.end_row();
+                            ui.label("");
+                            ui.colored_label(ui.visuals().error_fg_color, message);
+                        }
                     } else {
                         ui.label("Value (decimal): ").on_hover_text(hover_text);
                         ui.label("None");
@@ -203,24 +424,53 @@ impl MemoryEditor {
         }
     }
 
-    fn read_mem_value<T: ?Sized>(
-        mem: &mut T,
-        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+    /// Decode the value at `address`, or `None` if any of its bytes couldn't be read or are outside the address range.
+    fn read_mem_value<S: MemorySource + ?Sized>(
+        memory_cache: &mut MemoryCache,
+        source: &mut S,
         address: Address,
         data_preview: DataPreviewOptions,
         address_space: &Range<Address>,
-    ) -> String {
-        let bytes = (0..data_preview.selected_data_format.bytes_to_read())
-            .map(|i| {
-                let read_address = address + i;
-                if address_space.contains(&read_address) {
-                    read_fn(mem, read_address).unwrap_or(0)
-                } else {
-                    0
-                }
+    ) -> Option<String> {
+        let format = data_preview.selected_data_format;
+        let width = format.bytes_to_read();
+        let bytes = (0..width)
+            .map_while(|i| match address.checked_add(i as Address) {
+                Some(read_address) if address_space.contains(&read_address) => memory_cache.read(source, read_address),
+                _ => None,
             })
             .collect::<Vec<u8>>();
 
-        crate::utilities::slice_to_decimal_string(data_preview, &bytes)
+        let is_complete = match format {
+            // Variable length values only need the bytes up to their last one.
+            DataFormatType::Uleb128 | DataFormatType::Sleb128 => {
+                bytes.len() == width || bytes.iter().any(|byte| byte & 0x80 == 0)
+            }
+            _ => bytes.len() == width,
+        };
+
+        is_complete.then(|| crate::utilities::slice_to_decimal_string(data_preview, &bytes))
+    }
+
+    /// Encode the decimal `text` as the number type of `data_preview` and write it at `address`, as a single
+    /// undoable edit.
+    fn write_mem_value<S: MemorySource + ?Sized>(
+        history: &mut EditHistory,
+        memory_cache: &mut MemoryCache,
+        source: &mut S,
+        address: Address,
+        data_preview: DataPreviewOptions,
+        address_space: &Range<Address>,
+        text: &str,
+    ) -> Result<(), String> {
+        let bytes = crate::utilities::decimal_string_to_bytes(data_preview, text)
+            .map_err(|e| format!("Not a valid {}: {}", data_preview.selected_data_format.name(), e))?;
+
+        if address.saturating_add(bytes.len() as Address) > address_space.end {
+            return Err("The value doesn't fit before the end of the address range".to_string());
+        }
+
+        history.write(source, memory_cache, "Edit value", (address..).zip(bytes));
+        Ok(())
     }
 }
@@ -0,0 +1,155 @@
+use std::ops::Range;
+
+use egui::{Align2, Context, Event, RichText};
+
+use crate::encoding;
+use crate::option_data::PendingPaste;
+use crate::selection::{Pane, Selection};
+use crate::{clipboard, Address, MemoryEditor, MemorySource};
+
+/// The maximum amount of bytes to show in the paste confirmation dialog.
+const PREVIEW_LENGTH: usize = 64;
+
+impl MemoryEditor {
+    /// Handle `Ctrl+V` at the edit cursor, or at the start of the selection if the editor has keyboard focus.
+    ///
+    /// Text pasted into the hex area is parsed as hex, text pasted into the ASCII area is encoded like typed
+    /// characters, with the character table of the address range or the selected text encoding.
+    pub(crate) fn handle_paste_input<S: MemorySource + ?Sized>(
+        &mut self,
+        ctx: &Context,
+        source: &mut S,
+        address_space: &Range<Address>,
+    ) {
+        let frame_data = &mut self.frame_data;
+        let selection_start = frame_data
+            .selection
+            .filter(|_| frame_data.has_keyboard_focus)
+            .map(|selection| selection.start());
+        let Some(address) = frame_data.selected_edit_address.or(selection_start) else {
+            return;
+        };
+
+        // Take the event, as the `TextEdit` of the edit cursor would otherwise insert it as well.
+        let pasted = ctx.input_mut(|i| {
+            let index = i.events.iter().position(|event| matches!(event, Event::Paste(_)))?;
+            match i.events.remove(index) {
+                Event::Paste(text) => Some(text),
+                _ => None,
+            }
+        });
+        let Some(text) = pasted else {
+            return;
+        };
+
+        let bytes = match frame_data.active_pane {
+            Pane::Hex => clipboard::parse_hex(&text),
+            Pane::Ascii => encoding::selected_decoder(&self.options, &self.character_tables)
+                .encode_str(&text)
+                .ok(),
+        };
+        let Some(bytes) = bytes.filter(|bytes| !bytes.is_empty()) else {
+            return;
+        };
+
+        frame_data.set_selected_edit_address(None, address_space);
+
+        if bytes.len() > self.options.paste_confirmation_threshold {
+            frame_data.pending_paste = Some(PendingPaste { address, bytes });
+        } else {
+            self.paste(source, address, &bytes, address_space);
+        }
+    }
+
+    /// Draw the dialog for confirming a paste which exceeded the
+    /// [`crate::option_data::MemoryEditorOptions::paste_confirmation_threshold`].
+    pub(crate) fn draw_paste_confirmation<S: MemorySource + ?Sized>(
+        &mut self,
+        ctx: &Context,
+        source: &mut S,
+        address_space: &Range<Address>,
+    ) {
+        let Some(pending) = &self.frame_data.pending_paste else {
+            return;
+        };
+
+        let mut confirmed = None;
+
+        egui::Window::new("Confirm paste")
+            .id(egui::Id::new(&self.window_name).with("paste_confirmation"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                let fitting = address_space
+                    .end
+                    .saturating_sub(pending.address)
+                    .min(pending.bytes.len() as Address) as usize;
+
+                ui.label(format!(
+                    "Paste {} bytes at {:#X}?",
+                    pending.bytes.len(),
+                    pending.address
+                ));
+
+                if fitting < pending.bytes.len() {
+                    ui.label(format!(
+                        "Only the first {} bytes fit in the address range, the rest will be discarded.",
+                        fitting
+                    ));
+                }
+
+                let mut preview = pending
+                    .bytes
+                    .iter()
+                    .take(PREVIEW_LENGTH)
+                    .map(|value| format!("{:02X}", value))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                if pending.bytes.len() > PREVIEW_LENGTH {
+                    preview.push_str(" ...");
+                }
+                ui.label(RichText::new(preview).text_style(self.options.memory_editor_text_style.clone()));
+
+                ui.horizontal(|ui| {
+                    if ui.button("Paste").clicked() {
+                        confirmed = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        confirmed = Some(false);
+                    }
+                });
+            });
+
+        match confirmed {
+            Some(true) => {
+                if let Some(pending) = self.frame_data.pending_paste.take() {
+                    self.paste(source, pending.address, &pending.bytes, address_space);
+                }
+            }
+            Some(false) => self.frame_data.pending_paste = None,
+            None => {}
+        }
+    }
+
+    /// Write `bytes` starting at `address`, clipped to the end of the address space, and select the pasted range.
+    ///
+    /// Addresses which aren't writable are skipped, the entire paste is undone as one.
+    fn paste<S: MemorySource + ?Sized>(
+        &mut self,
+        source: &mut S,
+        address: Address,
+        bytes: &[u8],
+        address_space: &Range<Address>,
+    ) {
+        let length = address_space.end.saturating_sub(address).min(bytes.len() as Address) as usize;
+        let writes = (address..).zip(bytes[..length].iter().copied());
+
+        self.history.write(source, &mut self.memory_cache, "Paste", writes);
+
+        if length > 0 {
+            self.frame_data
+                .select(Selection::from_range(&(address..address + length as Address)));
+        }
+    }
+}
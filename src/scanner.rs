@@ -0,0 +1,543 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use egui::{Context, Ui};
+
+use crate::memory_cache;
+use crate::option_data::DataPreviewOptions;
+use crate::selection::Selection;
+use crate::utilities::AddressRangeExt;
+use crate::{utilities, Address, MemoryEditor, MemorySource};
+
+/// The amount of addresses (or candidates) checked per read from the [`MemorySource`].
+const SCAN_CHUNK_SIZE: usize = 64 * 1024;
+/// Scans for an unknown value keep a copy of the entire range, so they're limited to this many bytes.
+const MAX_UNKNOWN_SCAN_LENGTH: usize = 64 * 1024 * 1024;
+/// The maximum amount of candidates kept by a scan, the rest of the range isn't scanned once it's reached.
+const MAX_SCAN_CANDIDATES: usize = 1_000_000;
+
+/// How the values found by a scan are compared.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum ScanMode {
+    /// Keep every address, to compare against in the next scan.
+    Unknown,
+    /// Keep the addresses whose value equals the entered value.
+    Exact,
+    Changed,
+    Unchanged,
+    Increased,
+    Decreased,
+}
+
+impl ScanMode {
+    /// The modes available for the first scan, where there are no previous values to compare against.
+    const FIRST_SCAN: [ScanMode; 2] = [ScanMode::Unknown, ScanMode::Exact];
+    const NEXT_SCAN: [ScanMode; 5] = [
+        ScanMode::Exact,
+        ScanMode::Changed,
+        ScanMode::Unchanged,
+        ScanMode::Increased,
+        ScanMode::Decreased,
+    ];
+
+    const fn name(&self) -> &'static str {
+        match self {
+            ScanMode::Unknown => "Unknown initial value",
+            ScanMode::Exact => "Exact value",
+            ScanMode::Changed => "Changed value",
+            ScanMode::Unchanged => "Unchanged value",
+            ScanMode::Increased => "Increased value",
+            ScanMode::Decreased => "Decreased value",
+        }
+    }
+
+    /// Whether the `current` value of an address should be kept, where `previous` is its value during the last scan.
+    fn matches(&self, format: DataPreviewOptions, exact: &[u8], current: &[u8], previous: Option<&[u8]>) -> bool {
+        // Only compare the bytes which are part of a variable length value.
+        let data_format = format.selected_data_format;
+        let current = &current[..data_format.encoded_length(current)];
+        let previous = previous.map(|previous| &previous[..data_format.encoded_length(previous)]);
+
+        match (self, previous) {
+            (ScanMode::Unknown, _) => true,
+            (ScanMode::Exact, _) => current == exact,
+            (ScanMode::Changed, Some(previous)) => current != previous,
+            (ScanMode::Unchanged, Some(previous)) => current == previous,
+            (ScanMode::Increased, Some(previous)) => {
+                utilities::compare_values(format, current, previous) == Some(Ordering::Greater)
+            }
+            (ScanMode::Decreased, Some(previous)) => {
+                utilities::compare_values(format, current, previous) == Some(Ordering::Less)
+            }
+            (_, None) => false,
+        }
+    }
+}
+
+/// The addresses which are still in the running after a scan.
+#[derive(Debug, Clone, Default)]
+enum Candidates {
+    /// No scan has been made yet.
+    #[default]
+    None,
+    /// Every address within `range`, with the memory at the time of the scan. Made by scanning for an unknown value.
+    Snapshot {
+        range: Range<Address>,
+        memory: Vec<Option<u8>>,
+    },
+    /// The addresses which matched every scan so far, with their values (of `width` bytes each) at the time of
+    /// the last scan.
+    List { addresses: Vec<Address>, values: Vec<u8> },
+}
+
+/// A scan which is performed a chunk at a time, so that it can be spread out over multiple frames.
+#[derive(Debug, Clone)]
+struct ScanJob {
+    mode: ScanMode,
+    /// The encoded value for [`ScanMode::Exact`].
+    exact: Vec<u8>,
+    /// The range to scan, if this is the first scan.
+    range: Range<Address>,
+    previous: Candidates,
+    next: Candidates,
+    /// How far along the range (or list of previous candidates) the scan is.
+    position: u64,
+    total: u64,
+}
+
+impl ScanJob {
+    fn is_finished(&self) -> bool {
+        self.position >= self.total || self.is_full()
+    }
+
+    fn is_full(&self) -> bool {
+        matches!(&self.next, Candidates::List { addresses, .. } if addresses.len() >= MAX_SCAN_CANDIDATES)
+    }
+}
+
+/// A cheat-engine style scanner, which narrows down the addresses holding a value by repeatedly scanning for
+/// changes to it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ValueScanner {
+    /// The format of the values, fixed by the first scan.
+    format: DataPreviewOptions,
+    /// Whether to only consider addresses which are a multiple of the size of the value.
+    aligned: bool,
+    mode: Option<ScanMode>,
+    value: String,
+    candidates: Candidates,
+    job: Option<ScanJob>,
+    message: Option<String>,
+    /// Addresses the user wants to keep an eye on.
+    watch_list: Vec<Address>,
+}
+
+impl ValueScanner {
+    #[inline]
+    fn width(&self) -> usize {
+        self.format.selected_data_format.bytes_to_read()
+    }
+
+    #[inline]
+    fn is_aligned(&self, address: Address) -> bool {
+        !self.aligned || address % self.width() as Address == 0
+    }
+
+    fn has_scanned(&self) -> bool {
+        !matches!(self.candidates, Candidates::None)
+    }
+
+    fn candidate_count(&self) -> usize {
+        match &self.candidates {
+            Candidates::None => 0,
+            Candidates::Snapshot { range, .. } => (range.start..range.end.saturating_sub(self.width() as Address - 1))
+                .filter(|&address| self.is_aligned(address))
+                .count(),
+            Candidates::List { addresses, .. } => addresses.len(),
+        }
+    }
+
+    /// Start a scan of `range`, or of the previous candidates if a scan has been made before.
+    fn start(&mut self, mode: ScanMode, range: Range<Address>) -> Result<(), String> {
+        let exact = if mode == ScanMode::Exact {
+            utilities::decimal_string_to_bytes(self.format, &self.value)?
+        } else {
+            Vec::new()
+        };
+
+        let total = match &self.candidates {
+            Candidates::None if mode == ScanMode::Unknown && range.length() > MAX_UNKNOWN_SCAN_LENGTH as Address => {
+                return Err(format!(
+                    "Scans for an unknown value are limited to {} MiB, select a smaller range",
+                    MAX_UNKNOWN_SCAN_LENGTH / (1024 * 1024)
+                ));
+            }
+            Candidates::None => range.length(),
+            Candidates::Snapshot { range, .. } => range.length(),
+            Candidates::List { addresses, .. } => addresses.len() as u64,
+        };
+
+        let next = if mode == ScanMode::Unknown {
+            Candidates::Snapshot {
+                range: range.clone(),
+                memory: Vec::with_capacity(range.length() as usize),
+            }
+        } else {
+            Candidates::List {
+                addresses: Vec::new(),
+                values: Vec::new(),
+            }
+        };
+
+        self.job = Some(ScanJob {
+            mode,
+            exact,
+            range,
+            previous: std::mem::take(&mut self.candidates),
+            next,
+            position: 0,
+            total,
+        });
+        self.message = None;
+
+        Ok(())
+    }
+
+    /// Continue the scan in progress by `budget` addresses (or candidates), roughly.
+    fn run<S: MemorySource + ?Sized>(&mut self, source: &mut S, budget: usize) {
+        let mut scanned = 0;
+
+        while scanned < budget {
+            let Some(job) = &mut self.job else {
+                return;
+            };
+
+            if job.is_finished() {
+                let job = self.job.take().unwrap();
+                let is_full = job.is_full();
+                self.candidates = job.next;
+                self.message = Some(match self.candidate_count() {
+                    count if is_full => format!("Stopped after {} candidates", count),
+                    1 => "1 candidate".to_string(),
+                    count => format!("{} candidates", count),
+                });
+                return;
+            }
+
+            self.step(source);
+            scanned += SCAN_CHUNK_SIZE;
+        }
+    }
+
+    /// Scan the next chunk of the job in progress.
+    fn step<S: MemorySource + ?Sized>(&mut self, source: &mut S) {
+        let width = self.width();
+        let format = self.format;
+        let aligned = self.aligned;
+        let Some(job) = &mut self.job else {
+            return;
+        };
+
+        let chunk = job.position..job.position.saturating_add(SCAN_CHUNK_SIZE as u64).min(job.total);
+        job.position = chunk.end;
+
+        let Candidates::List { addresses, values } = &mut job.next else {
+            // Scans for an unknown value only have to take a snapshot of the memory.
+            if let Candidates::Snapshot { memory, .. } = &mut job.next {
+                let start = job.range.start + chunk.start;
+                memory.extend(memory_cache::peek_range(source, start..job.range.start + chunk.end));
+            }
+            return;
+        };
+
+        let mut keep = |address: Address, current: &[Option<u8>], previous: Option<&[u8]>| {
+            let Some(current) = current.iter().copied().collect::<Option<Vec<u8>>>() else {
+                return;
+            };
+
+            if addresses.len() < MAX_SCAN_CANDIDATES
+                && (!aligned || address % width as Address == 0)
+                && job.mode.matches(format, &job.exact, &current, previous)
+            {
+                addresses.push(address);
+                values.extend(current);
+            }
+        };
+
+        match &job.previous {
+            Candidates::None | Candidates::Snapshot { .. } => {
+                let (range, snapshot) = match &job.previous {
+                    Candidates::Snapshot { range, memory } => (range, Some(memory)),
+                    _ => (&job.range, None),
+                };
+                // Read enough to get the whole value at the last address of the chunk.
+                let start = range.start + chunk.start;
+                let read_end = (range.start + chunk.end)
+                    .saturating_add(width as Address - 1)
+                    .min(range.end);
+                let memory = memory_cache::peek_range(source, start..read_end);
+                // Snapshots are limited to `MAX_UNKNOWN_SCAN_LENGTH`, so their offsets fit in a `usize`.
+                let snapshot_start = chunk.start as usize;
+
+                for offset in 0..chunk.length() as usize {
+                    let Some(current) = memory.get(offset..offset + width) else {
+                        break;
+                    };
+                    let previous = snapshot
+                        .and_then(|snapshot| snapshot.get(snapshot_start + offset..snapshot_start + offset + width))
+                        .and_then(|previous| previous.iter().copied().collect::<Option<Vec<u8>>>());
+
+                    if snapshot.is_some() && previous.is_none() {
+                        continue;
+                    }
+
+                    keep(start + offset as Address, current, previous.as_deref());
+                }
+            }
+            Candidates::List {
+                addresses: previous_addresses,
+                values: previous_values,
+            } => {
+                for index in chunk.start as usize..chunk.end as usize {
+                    let address = previous_addresses[index];
+                    let current = memory_cache::peek_range(source, address..address.saturating_add(width as Address));
+                    let previous = &previous_values[index * width..(index + 1) * width];
+
+                    keep(address, &current, Some(previous));
+                }
+            }
+        }
+    }
+}
+
+impl MemoryEditor {
+    /// Continue the scan in progress, if any.
+    ///
+    /// This is done outside of the options area, so scans continue while it's collapsed.
+    pub(crate) fn handle_value_scan<S: MemorySource + ?Sized>(&mut self, ctx: &Context, source: &mut S) {
+        let scanner = &mut self.frame_data.value_scanner;
+        scanner.run(source, self.options.search_bytes_per_frame);
+
+        // Keep the frames coming, even if the user isn't interacting with the UI.
+        if scanner.job.is_some() {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Draw the value scanner underneath a collapsing header.
+    pub(crate) fn draw_value_scanner<S: MemorySource + ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        current_address_range: &Range<Address>,
+        source: &mut S,
+    ) {
+        egui::CollapsingHeader::new("🎯 Value Scanner")
+            .default_open(false)
+            .show(ui, |ui| {
+                let scan_range = self
+                    .frame_data
+                    .selection
+                    .filter(|selection| selection.is_multi_byte())
+                    .map_or(current_address_range.clone(), |selection| selection.range());
+                let scanner = &mut self.frame_data.value_scanner;
+                let has_scanned = scanner.has_scanned() || scanner.job.is_some();
+
+                // The format can only be changed in the data preview before the first scan.
+                if !has_scanned {
+                    scanner.format = self.options.data_preview;
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{:?} {} values in {:#X?}",
+                        scanner.format.selected_endianness,
+                        scanner.format.selected_data_format.name(),
+                        scan_range
+                    ))
+                    .on_hover_text(
+                        "The values are interpreted as set in the data preview, \
+                        select a range of addresses to only scan those",
+                    );
+
+                    ui.add_enabled(!has_scanned, egui::Checkbox::new(&mut scanner.aligned, "Aligned"))
+                        .on_hover_text("Only scan addresses which are a multiple of the size of the value");
+                });
+
+                let modes = if has_scanned {
+                    &ScanMode::NEXT_SCAN[..]
+                } else {
+                    &ScanMode::FIRST_SCAN[..]
+                };
+                let mut mode = scanner.mode.filter(|mode| modes.contains(mode)).unwrap_or(modes[0]);
+
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("ScanModeCombo")
+                        .selected_text(mode.name())
+                        .show_ui(ui, |ui| {
+                            for &option in modes {
+                                ui.selectable_value(&mut mode, option, option.name());
+                            }
+                        });
+
+                    if mode == ScanMode::Exact {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut scanner.value)
+                                .desired_width(100.0)
+                                .hint_text("0"),
+                        );
+                    }
+
+                    let scan_button = ui.add_enabled(
+                        scanner.job.is_none(),
+                        egui::Button::new(if has_scanned { "Next scan" } else { "First scan" }),
+                    );
+
+                    if scan_button.clicked() {
+                        if let Err(message) = scanner.start(mode, scan_range.clone()) {
+                            scanner.message = Some(message);
+                        }
+                    }
+
+                    if ui.add_enabled(has_scanned, egui::Button::new("Reset")).clicked() {
+                        scanner.job = None;
+                        scanner.candidates = Candidates::None;
+                        scanner.message = None;
+                    }
+                });
+
+                scanner.mode = Some(mode);
+
+                if let Some(job) = &scanner.job {
+                    let cancelled = ui
+                        .horizontal(|ui| {
+                            let progress = job.position as f32 / job.total.max(1) as f32;
+                            ui.add(egui::ProgressBar::new(progress).desired_width(200.0).show_percentage());
+
+                            ui.button("Cancel").clicked()
+                        })
+                        .inner;
+
+                    if cancelled {
+                        // Nothing was narrowed down yet, so the previous candidates are still valid.
+                        let job = scanner.job.take().unwrap();
+                        scanner.candidates = job.previous;
+                        scanner.message = Some("Scan cancelled".to_string());
+                    }
+                } else if let Some(message) = &scanner.message {
+                    ui.label(message);
+                }
+
+                self.draw_scan_results(ui, current_address_range, source);
+            });
+    }
+
+    /// Draw the candidates of the last scan, and the watch list.
+    fn draw_scan_results<S: MemorySource + ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        current_address_range: &Range<Address>,
+        source: &mut S,
+    ) {
+        let frame_data = &mut self.frame_data;
+        let memory_cache = &mut self.memory_cache;
+        let scanner = &mut frame_data.value_scanner;
+        let width = scanner.width();
+        let format = scanner.format;
+        let address_characters = utilities::address_characters(current_address_range);
+        let row_height = ui.spacing().interact_size.y;
+        let mut jump_to = None;
+
+        let mut read_value = |address: Address| {
+            let bytes = (0..width)
+                .map(|i| {
+                    address
+                        .checked_add(i as Address)
+                        .and_then(|address| memory_cache.read(source, address))
+                })
+                .collect::<Option<Vec<u8>>>();
+            bytes.map_or("--".to_string(), |bytes| {
+                utilities::slice_to_decimal_string(format, &bytes)
+            })
+        };
+
+        if let Candidates::List { addresses, values } = &scanner.candidates {
+            let watch_list = &mut scanner.watch_list;
+
+            egui::ScrollArea::vertical()
+                .id_salt("scan_results")
+                .max_height(row_height * 8.0)
+                .auto_shrink([false, true])
+                .show_rows(ui, row_height, addresses.len(), |ui, rows| {
+                    egui::Grid::new("scan_results_grid").num_columns(4).show(ui, |ui| {
+                        for index in rows {
+                            let address = addresses[index];
+                            let previous = &values[index * width..(index + 1) * width];
+                            let text = format!("0x{:01$X}", address, address_characters);
+
+                            if ui
+                                .selectable_label(false, text)
+                                .on_hover_text("Jump to address")
+                                .clicked()
+                            {
+                                jump_to = Some(address);
+                            }
+
+                            ui.label(read_value(address));
+                            ui.label(utilities::slice_to_decimal_string(format, previous))
+                                .on_hover_text("The value during the last scan");
+
+                            if ui.small_button("Watch").clicked() && !watch_list.contains(&address) {
+                                watch_list.push(address);
+                            }
+
+                            ui.end_row();
+                        }
+                    });
+                });
+        }
+
+        if !scanner.watch_list.is_empty() {
+            ui.label("Watch list");
+
+            let mut remove = None;
+            egui::Grid::new("watch_list_grid").num_columns(3).show(ui, |ui| {
+                for (index, &address) in scanner.watch_list.iter().enumerate() {
+                    let text = format!("0x{:01$X}", address, address_characters);
+
+                    if ui
+                        .selectable_label(false, text)
+                        .on_hover_text("Jump to address")
+                        .clicked()
+                    {
+                        jump_to = Some(address);
+                    }
+
+                    ui.label(read_value(address));
+
+                    if ui
+                        .small_button("🗑")
+                        .on_hover_text("Remove from the watch list")
+                        .clicked()
+                    {
+                        remove = Some(index);
+                    }
+
+                    ui.end_row();
+                }
+            });
+
+            if let Some(index) = remove {
+                scanner.watch_list.remove(index);
+            }
+        }
+
+        // Candidates may be from another address range.
+        if let Some(address) = jump_to.filter(|address| current_address_range.contains(address)) {
+            let end = address.saturating_add(width as Address).min(current_address_range.end);
+            frame_data.set_selected_edit_address(None, current_address_range);
+            frame_data.select(Selection::from_range(&(address..end)));
+            frame_data.goto_address_line =
+                Some((address - current_address_range.start) / self.options.row_length() as Address);
+        }
+    }
+}
@@ -0,0 +1,889 @@
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use egui::{Context, Id, Ui};
+
+use crate::encoding::{self, CharacterDecoder, CharacterTable};
+use crate::memory_cache;
+use crate::memory_source::SharedMemorySource;
+use crate::option_data::{Endianness, SearchKind, SearchOptions, TextEncoding};
+use crate::option_ui::data_format_combo;
+use crate::selection::Selection;
+use crate::utilities::AddressRangeExt;
+use crate::{utilities, Address, MemoryEditor, MemorySource};
+
+/// The amount of match positions checked per read from the [`MemorySource`].
+const SEARCH_CHUNK_SIZE: usize = 64 * 1024;
+/// Searching for all matches stops after this many, to bound the memory used by the results.
+pub(crate) const MAX_SEARCH_RESULTS: usize = 100_000;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum SearchDirection {
+    Forward,
+    Backward,
+}
+
+/// A search requested through the search bar or `F3`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum SearchRequest {
+    Find(SearchDirection),
+    FindAll,
+    /// Replace the selected match, if any, and find the next one.
+    Replace,
+    ReplaceAll,
+    /// Count the matches "Replace all" would replace, without writing anything.
+    CountReplacements,
+}
+
+/// A replacement of all matches, performed once the search for them has finished.
+#[derive(Debug, Clone)]
+pub(crate) struct ReplaceAll {
+    replacement: Vec<Option<u8>>,
+    dry_run: bool,
+}
+
+/// A sequence of bytes to search for, where `None` matches any byte.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SearchPattern {
+    bytes: Vec<Option<u8>>,
+    /// Which bytes are ASCII letters whose case is ignored, as opposed to e.g. the trail byte of a Shift-JIS character
+    /// which happens to have the same value. Empty for case sensitive searches.
+    ignore_case: Vec<bool>,
+}
+
+impl SearchPattern {
+    /// Interpret `query` according to the `options`, returning a message for the user if it isn't valid.
+    ///
+    /// Text is encoded with the `table` if [`SearchOptions::use_character_table`] is set.
+    pub fn parse(query: &str, options: &SearchOptions, table: Option<&CharacterTable>) -> Result<Self, String> {
+        let bytes = parse_query(query, options, table)?;
+        let table = table.filter(|_| options.use_character_table);
+        // Case is ignored by comparing ASCII letters, which EBCDIC and character tables don't use.
+        let case_sensitive = options.kind != SearchKind::Text
+            || options.case_sensitive
+            || options.text_encoding == TextEncoding::Ebcdic
+            || table.is_some();
+
+        if bytes.is_empty() {
+            return Err("Nothing to search for".to_string());
+        }
+
+        let ignore_case = if case_sensitive {
+            Vec::new()
+        } else {
+            // Encoded character by character, like `encode_str`, so the bytes line up with the pattern.
+            query
+                .chars()
+                .flat_map(|character| {
+                    let encoded = options.text_encoding.encode(character).unwrap_or_default();
+                    encoded
+                        .into_iter()
+                        .map(move |byte| character.is_ascii_alphabetic() && byte == character as u8)
+                })
+                .collect()
+        };
+
+        Ok(SearchPattern { bytes, ignore_case })
+    }
+
+    /// Interpret `query` as the replacement for the matches of this pattern, where `None` keeps the matched byte.
+    ///
+    /// Replacements of a different length are only accepted if [`SearchOptions::fit_replacement`] is set, in which
+    /// case they're padded with `0x00` or truncated.
+    pub fn parse_replacement(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+        table: Option<&CharacterTable>,
+    ) -> Result<Vec<Option<u8>>, String> {
+        let mut replacement = parse_query(query, options, table)?;
+
+        if replacement.len() != self.len() {
+            if !options.fit_replacement {
+                return Err(format!(
+                    "The replacement is {} bytes, but the matches are {} bytes. Enable padding to replace them anyway",
+                    replacement.len(),
+                    self.len()
+                ));
+            }
+
+            replacement.resize(self.len(), Some(0));
+        }
+
+        Ok(replacement)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether the pattern matches the start of `memory`, memory which couldn't be read never matches.
+    fn matches(&self, memory: &[Option<u8>]) -> bool {
+        memory.len() >= self.bytes.len()
+            && self
+                .bytes
+                .iter()
+                .zip(memory)
+                .enumerate()
+                .all(|(i, (expected, actual))| match (expected, actual) {
+                    (None, Some(_)) => true,
+                    (Some(expected), Some(actual)) if self.ignore_case.get(i) == Some(&true) => {
+                        expected.eq_ignore_ascii_case(actual)
+                    }
+                    (Some(expected), Some(actual)) => expected == actual,
+                    (_, None) => false,
+                })
+    }
+}
+
+/// A search which checks a chunk of match positions at a time, so that it can be spread out over multiple frames.
+#[derive(Debug, Clone)]
+pub(crate) struct SearchJob {
+    pattern: SearchPattern,
+    direction: SearchDirection,
+    /// Whether to collect every match, rather than stopping at the first one.
+    find_all: bool,
+    /// The match positions which still have to be checked, in the order they'll be checked.
+    remaining: VecDeque<Range<Address>>,
+    /// The address range which is searched, matches can't extend past its end.
+    address_space: Range<Address>,
+    /// The total amount of match positions, as a float since it can exceed `usize` for the entire address space.
+    total: f64,
+    checked: f64,
+    results: Vec<Address>,
+}
+
+impl SearchJob {
+    /// Search `address_space` for the first match in `direction` from `from`, wrapping around at its ends.
+    ///
+    /// Searching forward finds matches starting at `from`, searching backward finds matches starting before it.
+    pub fn find(
+        pattern: SearchPattern,
+        address_space: &Range<Address>,
+        from: Address,
+        direction: SearchDirection,
+    ) -> Self {
+        let from = from.clamp(address_space.start, address_space.end);
+        let segments = match direction {
+            SearchDirection::Forward => [from..address_space.end, address_space.start..from],
+            SearchDirection::Backward => [address_space.start..from, from..address_space.end],
+        };
+
+        Self::new(pattern, address_space, segments, direction, false)
+    }
+
+    /// Search `address_space` for all matches, from start to end.
+    pub fn find_all(pattern: SearchPattern, address_space: &Range<Address>) -> Self {
+        Self::new(
+            pattern,
+            address_space,
+            [address_space.clone()],
+            SearchDirection::Forward,
+            true,
+        )
+    }
+
+    fn new(
+        pattern: SearchPattern,
+        address_space: &Range<Address>,
+        segments: impl IntoIterator<Item = Range<Address>>,
+        direction: SearchDirection,
+        find_all: bool,
+    ) -> Self {
+        // A match has to fit before the end of the address space.
+        let last_start = address_space.end.saturating_sub(pattern.len() as Address - 1);
+        let remaining: VecDeque<_> = segments
+            .into_iter()
+            .map(|segment| segment.start..segment.end.min(last_start))
+            .filter(|segment| !segment.is_empty())
+            .collect();
+
+        SearchJob {
+            pattern,
+            direction,
+            find_all,
+            total: remaining.iter().map(|segment| segment.length() as f64).sum(),
+            remaining,
+            address_space: address_space.clone(),
+            checked: 0.0,
+            results: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn pattern(&self) -> &SearchPattern {
+        &self.pattern
+    }
+
+    /// The matches found so far, in the order they were found.
+    #[inline]
+    pub fn results(&self) -> &[Address] {
+        &self.results
+    }
+
+    /// The fraction of match positions checked so far.
+    pub fn progress(&self) -> f32 {
+        if self.total > 0.0 {
+            (self.checked / self.total) as f32
+        } else {
+            1.0
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.remaining.is_empty()
+            || (!self.find_all && !self.results.is_empty())
+            || self.results.len() >= MAX_SEARCH_RESULTS
+    }
+
+    /// Check the next chunk of match positions.
+    pub fn step<S: MemorySource + ?Sized>(&mut self, source: &mut S) {
+        if self.is_finished() {
+            return;
+        }
+
+        let Some(segment) = self.remaining.front_mut() else {
+            return;
+        };
+
+        let chunk_size = SEARCH_CHUNK_SIZE as Address;
+        let chunk = match self.direction {
+            SearchDirection::Forward => {
+                let chunk = segment.start..segment.start.saturating_add(chunk_size).min(segment.end);
+                segment.start = chunk.end;
+                chunk
+            }
+            SearchDirection::Backward => {
+                let chunk = segment.end.saturating_sub(chunk_size).max(segment.start)..segment.end;
+                segment.end = chunk.start;
+                chunk
+            }
+        };
+
+        if segment.start >= segment.end {
+            self.remaining.pop_front();
+        }
+
+        // Read enough to check a match starting at the last position of the chunk.
+        let pattern_length = self.pattern.len() as Address;
+        let read_end = chunk.end.saturating_add(pattern_length - 1).min(self.address_space.end);
+        let memory = memory_cache::peek_range(source, chunk.start..read_end);
+        let is_match = |offset: &usize| self.pattern.matches(&memory[*offset..]);
+        // Chunks are at most `SEARCH_CHUNK_SIZE` long.
+        let offsets = 0..chunk.length() as usize;
+        let to_address = |offset: usize| chunk.start + offset as Address;
+
+        match (self.direction, self.find_all) {
+            (SearchDirection::Forward, true) => {
+                let room = MAX_SEARCH_RESULTS - self.results.len();
+                let matches = offsets.filter(is_match).take(room).map(to_address);
+                self.results.extend(matches);
+            }
+            (SearchDirection::Forward, false) => self.results.extend(offsets.clone().find(is_match).map(to_address)),
+            (SearchDirection::Backward, _) => self.results.extend(offsets.clone().rfind(is_match).map(to_address)),
+        }
+
+        self.checked += chunk.length() as f64;
+    }
+
+    /// Check at most `budget` match positions, roughly.
+    pub fn run<S: MemorySource + ?Sized>(&mut self, source: &mut S, budget: usize) {
+        let mut checked = 0;
+
+        while !self.is_finished() && checked < budget {
+            self.step(source);
+            checked += SEARCH_CHUNK_SIZE;
+        }
+    }
+}
+
+/// A [`SearchJob`] which is run by a worker thread on a [`SharedMemorySource`], see
+/// [`MemoryEditor::set_search_source`].
+#[derive(Debug)]
+pub(crate) struct BackgroundSearch {
+    /// The address range of the job, which is moved to the worker.
+    address_space: Range<Address>,
+    cancelled: AtomicBool,
+    /// The progress of the job, as the bits of an `f32`.
+    progress: AtomicU32,
+    /// The job, once it has finished.
+    finished: Mutex<Option<SearchJob>>,
+}
+
+impl BackgroundSearch {
+    pub fn spawn(mut job: SearchJob, source: SharedMemorySource) -> Arc<Self> {
+        let search = Arc::new(BackgroundSearch {
+            address_space: job.address_space.clone(),
+            cancelled: AtomicBool::new(false),
+            progress: AtomicU32::new(0.0f32.to_bits()),
+            finished: Mutex::new(None),
+        });
+        let worker = search.clone();
+
+        std::thread::spawn(move || {
+            while !worker.cancelled.load(Ordering::Relaxed) {
+                if job.is_finished() {
+                    *worker.finished.lock().unwrap_or_else(PoisonError::into_inner) = Some(job);
+                    return;
+                }
+
+                // The source is only locked for a single chunk, as the UI needs it to draw every frame.
+                job.step(&mut *source.lock().unwrap_or_else(PoisonError::into_inner));
+                worker.progress.store(job.progress().to_bits(), Ordering::Relaxed);
+            }
+        });
+
+        search
+    }
+
+    /// Take the job out of the search if the worker has finished it.
+    fn take_finished(&self) -> Option<SearchJob> {
+        self.finished.lock().unwrap_or_else(PoisonError::into_inner).take()
+    }
+}
+
+/// A search which is in progress.
+#[derive(Debug, Clone)]
+pub(crate) enum SearchTask {
+    /// Run a slice at a time, at the start of every frame.
+    Sliced(SearchJob),
+    /// Run on a worker thread.
+    Background(Arc<BackgroundSearch>),
+}
+
+impl SearchTask {
+    pub fn progress(&self) -> f32 {
+        match self {
+            SearchTask::Sliced(job) => job.progress(),
+            SearchTask::Background(search) => f32::from_bits(search.progress.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// The address range which is searched.
+    pub fn address_space(&self) -> &Range<Address> {
+        match self {
+            SearchTask::Sliced(job) => &job.address_space,
+            SearchTask::Background(search) => &search.address_space,
+        }
+    }
+
+    pub fn cancel(&self) {
+        if let SearchTask::Background(search) = self {
+            search.cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Parse hex bytes separated by optional whitespace, where `??` is a wildcard, e.g. `DE AD ?? EF` or `DEAD??EF`.
+pub(crate) fn parse_hex_pattern(text: &str) -> Result<Vec<Option<u8>>, String> {
+    let mut bytes = Vec::new();
+
+    for token in text.split_whitespace() {
+        let token = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+            .unwrap_or(token);
+
+        if token.len() % 2 != 0 || !token.is_ascii() {
+            return Err(format!("`{}` is not a whole number of bytes", token));
+        }
+
+        for pair in token.as_bytes().chunks(2) {
+            let pair = std::str::from_utf8(pair).unwrap();
+
+            if pair == "??" {
+                bytes.push(None);
+            } else {
+                let value = u8::from_str_radix(pair, 16).map_err(|_| format!("`{}` is not a hex byte", pair))?;
+                bytes.push(Some(value));
+            }
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Interpret `query` as a sequence of bytes according to the `options`, where `None` stands for any byte.
+fn parse_query(
+    query: &str,
+    options: &SearchOptions,
+    table: Option<&CharacterTable>,
+) -> Result<Vec<Option<u8>>, String> {
+    let bytes = match options.kind {
+        SearchKind::Hex => return parse_hex_pattern(query),
+        SearchKind::Text => encode_text(query, options, table)?,
+        SearchKind::Value => utilities::decimal_string_to_bytes(options.value_format, query)?,
+    };
+
+    Ok(bytes.into_iter().map(Some).collect())
+}
+
+/// The writes which replace the match at `address` with `replacement`.
+fn replacement_writes(address: Address, replacement: &[Option<u8>]) -> impl Iterator<Item = (Address, u8)> + '_ {
+    (address..)
+        .zip(replacement)
+        .filter_map(|(address, value)| value.map(|value| (address, value)))
+}
+
+/// Encode `text` in the encoding selected in the `options`, which can be the character `table`.
+fn encode_text(text: &str, options: &SearchOptions, table: Option<&CharacterTable>) -> Result<Vec<u8>, String> {
+    let table = table.filter(|_| options.use_character_table);
+    let result = match table {
+        Some(table) => table.encode_str(text),
+        None => options.text_encoding.encode_str(text),
+    };
+
+    result.map_err(|character| {
+        let encoding = table.map_or(options.text_encoding.name(), |_| "the character table");
+        format!("{:?} can't be encoded with {}", character, encoding)
+    })
+}
+
+impl MemoryEditor {
+    /// Draw the search bar underneath a collapsing header.
+    pub(crate) fn draw_search_options(&mut self, ui: &mut Ui, current_address_range: &Range<Address>) {
+        egui::CollapsingHeader::new("🔍 Search")
+            .default_open(false)
+            .show(ui, |ui| {
+                let has_table = encoding::selected_table(&self.options, &self.character_tables).is_some();
+                let search_options = &mut self.options.search;
+
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("SearchKindCombo")
+                        .selected_text(format!("{:?}", search_options.kind))
+                        .show_ui(ui, |ui| {
+                            for kind in SearchKind::iter() {
+                                ui.selectable_value(&mut search_options.kind, kind, format!("{:?}", kind));
+                            }
+                        })
+                        .response
+                        .on_hover_text("Select what to search for");
+
+                    let hint_text = match search_options.kind {
+                        SearchKind::Hex => "DE AD ?? EF",
+                        SearchKind::Text => "Text",
+                        SearchKind::Value => "0",
+                    };
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut self.frame_data.search_query)
+                            .id(search_field_id(&self.window_name))
+                            .hint_text(hint_text),
+                    );
+
+                    // Keep the focus after pressing enter, so the user can keep going to the next match.
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        let backward = ui.input(|i| i.modifiers.shift);
+                        self.frame_data.pending_search = Some(SearchRequest::Find(if backward {
+                            SearchDirection::Backward
+                        } else {
+                            SearchDirection::Forward
+                        }));
+                        response.request_focus();
+                    }
+
+                    if ui.button("⏶").on_hover_text("Find previous (Shift+F3)").clicked() {
+                        self.frame_data.pending_search = Some(SearchRequest::Find(SearchDirection::Backward));
+                    }
+
+                    if ui.button("⏷").on_hover_text("Find next (F3)").clicked() {
+                        self.frame_data.pending_search = Some(SearchRequest::Find(SearchDirection::Forward));
+                    }
+
+                    if ui.button("Find all").clicked() {
+                        self.frame_data.pending_search = Some(SearchRequest::FindAll);
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    let hint_text = match search_options.kind {
+                        SearchKind::Hex => "Replacement, ?? keeps a byte",
+                        SearchKind::Text | SearchKind::Value => "Replacement",
+                    };
+                    ui.add(egui::TextEdit::singleline(&mut self.frame_data.replace_query).hint_text(hint_text));
+
+                    if ui
+                        .button("Replace")
+                        .on_hover_text("Replace the selected match, and find the next one")
+                        .clicked()
+                    {
+                        self.frame_data.pending_search = Some(SearchRequest::Replace);
+                    }
+
+                    if ui.button("Replace all").clicked() {
+                        self.frame_data.pending_search = Some(SearchRequest::ReplaceAll);
+                    }
+
+                    if ui
+                        .button("Count")
+                        .on_hover_text("Count the matches which would be replaced, without replacing them")
+                        .clicked()
+                    {
+                        self.frame_data.pending_search = Some(SearchRequest::CountReplacements);
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut search_options.fit_replacement, "Pad or truncate")
+                        .on_hover_text(
+                            "Allow replacements of a different length, padding them with 00 or truncating them",
+                        );
+                    ui.checkbox(&mut search_options.replace_in_selection, "Only in selection")
+                        .on_hover_text("Only replace the matches inside the selection");
+                });
+
+                ui.horizontal(|ui| match search_options.kind {
+                    SearchKind::Hex => {
+                        ui.label("Use ?? to match any byte");
+                    }
+                    SearchKind::Text => {
+                        let use_table = search_options.use_character_table && has_table;
+
+                        egui::ComboBox::from_id_salt("SearchEncodingCombo")
+                            .selected_text(match use_table {
+                                true => "Character table",
+                                false => search_options.text_encoding.name(),
+                            })
+                            .show_ui(ui, |ui| {
+                                for encoding in TextEncoding::iter() {
+                                    let is_selected = !use_table && search_options.text_encoding == encoding;
+                                    if ui.selectable_label(is_selected, encoding.name()).clicked() {
+                                        search_options.text_encoding = encoding;
+                                        search_options.use_character_table = false;
+                                    }
+                                }
+
+                                // The character table of the current address range.
+                                if has_table {
+                                    ui.selectable_value(
+                                        &mut search_options.use_character_table,
+                                        true,
+                                        "Character table",
+                                    );
+                                }
+                            })
+                            .response
+                            .on_hover_text("Select the encoding of the text");
+
+                        ui.checkbox(&mut search_options.case_sensitive, "Case sensitive");
+                    }
+                    SearchKind::Value => {
+                        let value_format = &mut search_options.value_format;
+
+                        egui::ComboBox::from_id_salt("SearchEndiannessCombo")
+                            .selected_text(format!("{:?}", value_format.selected_endianness))
+                            .show_ui(ui, |ui| {
+                                for endian in Endianness::iter() {
+                                    ui.selectable_value(
+                                        &mut value_format.selected_endianness,
+                                        endian,
+                                        format!("{:?}", endian),
+                                    );
+                                }
+                            })
+                            .response
+                            .on_hover_text("Select the endianness of the value");
+
+                        data_format_combo(
+                            ui,
+                            egui::ComboBox::from_id_salt("SearchFormatCombo"),
+                            &mut value_format.selected_data_format,
+                        )
+                        .on_hover_text("Select the number type of the value");
+                    }
+                });
+
+                if let Some(task) = &self.frame_data.search_task {
+                    let cancelled = ui
+                        .horizontal(|ui| {
+                            ui.add(
+                                egui::ProgressBar::new(task.progress())
+                                    .desired_width(200.0)
+                                    .show_percentage(),
+                            );
+
+                            ui.button("Cancel").clicked()
+                        })
+                        .inner;
+
+                    if cancelled {
+                        task.cancel();
+                        self.frame_data.search_task = None;
+                        self.frame_data.pending_replace_all = None;
+                        self.frame_data.search_message = Some("Search cancelled".to_string());
+                    }
+                } else if let Some(message) = &self.frame_data.search_message {
+                    ui.label(message);
+                }
+
+                self.draw_search_results(ui, current_address_range);
+            });
+    }
+
+    /// Draw the list of matches found by the last search for all matches, clicking one jumps to it.
+    fn draw_search_results(&mut self, ui: &mut Ui, current_address_range: &Range<Address>) {
+        let frame_data = &mut self.frame_data;
+
+        if frame_data.search_results.is_empty() {
+            return;
+        }
+
+        let address_characters = utilities::address_characters(current_address_range);
+        let row_height = ui.text_style_height(&egui::TextStyle::Body);
+        let mut clicked = None;
+
+        egui::ScrollArea::vertical()
+            .id_salt("search_results")
+            .max_height(row_height * 6.0)
+            .auto_shrink([false, true])
+            .show_rows(ui, row_height, frame_data.search_results.len(), |ui, rows| {
+                for &address in &frame_data.search_results[rows] {
+                    let is_selected = frame_data
+                        .selection
+                        .is_some_and(|selection| selection.start() == address);
+                    let text = format!("0x{:01$X}", address, address_characters);
+
+                    if ui.selectable_label(is_selected, text).clicked() {
+                        clicked = Some(address);
+                    }
+                }
+            });
+
+        // The results may be from another address range.
+        if let Some(address) = clicked.filter(|address| current_address_range.contains(address)) {
+            let length = frame_data.search_results_length;
+            self.select_match(address, length, current_address_range);
+        }
+    }
+
+    /// Check for `F3` and `Shift+F3`, start any search requested through them or the search bar, and continue the
+    /// search which is in progress.
+    ///
+    /// Searches for a single match continue from the start of the current selection, and select the match.
+    pub(crate) fn handle_search_input<S: MemorySource + ?Sized>(
+        &mut self,
+        ctx: &Context,
+        source: &mut S,
+        address_space: &Range<Address>,
+    ) {
+        use egui::{Key, Modifiers};
+
+        let frame_data = &mut self.frame_data;
+        let search_focused = ctx.memory(|mem| mem.has_focus(search_field_id(&self.window_name)));
+
+        if frame_data.has_keyboard_focus || frame_data.selected_edit_address.is_some() || search_focused {
+            ctx.input_mut(|i| {
+                if i.consume_key(Modifiers::SHIFT, Key::F3) {
+                    frame_data.pending_search = Some(SearchRequest::Find(SearchDirection::Backward));
+                } else if i.consume_key(Modifiers::NONE, Key::F3) {
+                    frame_data.pending_search = Some(SearchRequest::Find(SearchDirection::Forward));
+                }
+            });
+        }
+
+        if let Some(request) = frame_data.pending_search.take() {
+            self.start_search(request, source, address_space);
+        }
+
+        let Some(task) = &mut self.frame_data.search_task else {
+            return;
+        };
+
+        // Matches are only valid for the address range they were searched in.
+        if task.address_space() != address_space {
+            task.cancel();
+            self.frame_data.search_task = None;
+            self.frame_data.pending_replace_all = None;
+            self.frame_data.search_message = Some("Search cancelled, the address range changed".to_string());
+            return;
+        }
+
+        let finished_job = match task {
+            SearchTask::Sliced(job) => {
+                job.run(source, self.options.search_bytes_per_frame);
+                job.is_finished().then(|| job.clone())
+            }
+            SearchTask::Background(search) => search.take_finished(),
+        };
+
+        match finished_job {
+            Some(job) => {
+                self.frame_data.search_task = None;
+                self.finish_search(job, source, address_space);
+            }
+            // Keep the frames coming, even if the user isn't interacting with the UI.
+            None => ctx.request_repaint(),
+        }
+    }
+
+    fn start_search<S: MemorySource + ?Sized>(
+        &mut self,
+        request: SearchRequest,
+        source: &mut S,
+        address_space: &Range<Address>,
+    ) {
+        let frame_data = &mut self.frame_data;
+        let options = &self.options.search;
+        let table = encoding::selected_table(&self.options, &self.character_tables);
+
+        let parsed = SearchPattern::parse(&frame_data.search_query, options, table).and_then(|pattern| {
+            let replacement = match request {
+                SearchRequest::Find(_) | SearchRequest::FindAll => Vec::new(),
+                _ => pattern.parse_replacement(&frame_data.replace_query, options, table)?,
+            };
+            Ok((pattern, replacement))
+        });
+        let (pattern, replacement) = match parsed {
+            Ok(parsed) => parsed,
+            Err(message) => {
+                frame_data.search_message = Some(message);
+                return;
+            }
+        };
+
+        frame_data.pending_replace_all = None;
+
+        let job = match request {
+            SearchRequest::Find(direction) => {
+                let from = match (frame_data.selection, direction) {
+                    (Some(selection), SearchDirection::Forward) => selection.start().saturating_add(1),
+                    (Some(selection), SearchDirection::Backward) => selection.start(),
+                    (None, SearchDirection::Forward) => address_space.start,
+                    (None, SearchDirection::Backward) => address_space.end,
+                };
+
+                SearchJob::find(pattern, address_space, from, direction)
+            }
+            SearchRequest::FindAll => SearchJob::find_all(pattern, address_space),
+            SearchRequest::Replace => {
+                let from = match frame_data.selection {
+                    // Only replace the selection if it's exactly a match, then continue after it.
+                    Some(selection)
+                        if selection.range().length() == pattern.len() as Address
+                            && pattern.matches(&memory_cache::peek_range(source, selection.range())) =>
+                    {
+                        let writes = replacement_writes(selection.start(), &replacement);
+                        self.history.write(source, &mut self.memory_cache, "Replace", writes);
+                        selection.range().end
+                    }
+                    Some(selection) => selection.start(),
+                    None => address_space.start,
+                };
+
+                SearchJob::find(pattern, address_space, from, SearchDirection::Forward)
+            }
+            SearchRequest::ReplaceAll | SearchRequest::CountReplacements => {
+                let range = match frame_data.selection {
+                    Some(selection) if options.replace_in_selection => selection.range(),
+                    _ => address_space.clone(),
+                };
+
+                frame_data.pending_replace_all = Some(ReplaceAll {
+                    replacement,
+                    dry_run: request == SearchRequest::CountReplacements,
+                });
+
+                SearchJob::find_all(pattern, &range)
+            }
+        };
+
+        if let Some(task) = frame_data.search_task.take() {
+            task.cancel();
+        }
+
+        frame_data.search_message = None;
+        frame_data.search_task = Some(match &self.search_source {
+            Some(source) => SearchTask::Background(BackgroundSearch::spawn(job, source.clone())),
+            None => SearchTask::Sliced(job),
+        });
+    }
+
+    fn finish_search<S: MemorySource + ?Sized>(
+        &mut self,
+        job: SearchJob,
+        source: &mut S,
+        address_space: &Range<Address>,
+    ) {
+        let length = job.pattern().len();
+
+        if let Some(replace_all) = self.frame_data.pending_replace_all.take() {
+            self.replace_matches(job, replace_all, source);
+        } else if job.find_all {
+            let count = job.results().len();
+            self.frame_data.search_message = Some(match count {
+                MAX_SEARCH_RESULTS.. => format!("Stopped after {} matches", count),
+                1 => "1 match".to_string(),
+                _ => format!("{} matches", count),
+            });
+            self.frame_data.search_results = job.results;
+            self.frame_data.search_results_length = length;
+        } else if let Some(&address) = job.results().first() {
+            self.frame_data.search_message = Some(format!("Found at {:#X}", address));
+            self.select_match(address, length, address_space);
+        } else {
+            self.frame_data.search_message = Some("No matches".to_string());
+        }
+    }
+
+    /// Replace all matches found by `job` as a single undoable operation, skipping matches which overlap a previous
+    /// one, or only count them if it's a dry run.
+    ///
+    /// The memory may have changed while the search was running, so matches which no longer match are skipped too.
+    fn replace_matches<S: MemorySource + ?Sized>(&mut self, job: SearchJob, replace_all: ReplaceAll, source: &mut S) {
+        let length = job.pattern().len();
+        let mut next_free = Address::MIN;
+        let matches: Vec<Address> = job
+            .results()
+            .iter()
+            .copied()
+            .filter(|&address| {
+                let end = address.saturating_add(length as Address);
+                job.pattern().matches(&memory_cache::peek_range(source, address..end))
+            })
+            .filter(|&address| {
+                let overlaps = address < next_free;
+                if !overlaps {
+                    next_free = address + length as Address;
+                }
+                !overlaps
+            })
+            .collect();
+
+        let count = match matches.len() {
+            1 => "1 match".to_string(),
+            count => format!("{} matches", count),
+        };
+        let limit_note = if job.results().len() >= MAX_SEARCH_RESULTS {
+            ", stopped at the limit"
+        } else {
+            ""
+        };
+
+        self.frame_data.search_message = Some(if replace_all.dry_run {
+            format!("Would replace {}{}", count, limit_note)
+        } else {
+            let writes = matches
+                .iter()
+                .flat_map(|&address| replacement_writes(address, &replace_all.replacement));
+            self.history
+                .write(source, &mut self.memory_cache, "Replace all", writes);
+
+            format!("Replaced {}{}", count, limit_note)
+        });
+    }
+
+    /// Select the match at `address` and scroll it into view.
+    fn select_match(&mut self, address: Address, length: usize, address_space: &Range<Address>) {
+        let frame_data = &mut self.frame_data;
+
+        frame_data.set_selected_edit_address(None, address_space);
+        frame_data.select(Selection::from_range(&(address..address + length as Address)));
+        frame_data.goto_address_line = Some((address - address_space.start) / self.options.row_length() as Address);
+    }
+}
+
+fn search_field_id(window_name: &str) -> Id {
+    Id::new(window_name).with("search_query")
+}
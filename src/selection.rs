@@ -0,0 +1,67 @@
+use std::ops::Range;
+
+use crate::Address;
+
+/// The two areas in which values are displayed.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Pane {
+    #[default]
+    Hex,
+    Ascii,
+}
+
+/// A contiguous range of selected addresses.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Selection {
+    /// The address where the selection was started, this stays fixed while the selection is extended.
+    pub anchor: Address,
+    /// The address the selection was extended to, e.g. by dragging or with Shift+arrow keys.
+    pub cursor: Address,
+}
+
+impl Selection {
+    /// Create a selection of a single address.
+    pub fn new(address: Address) -> Self {
+        Selection {
+            anchor: address,
+            cursor: address,
+        }
+    }
+
+    /// Create a selection spanning the given (non-empty) range.
+    pub fn from_range(range: &Range<Address>) -> Self {
+        Selection {
+            anchor: range.start,
+            cursor: range.end - 1,
+        }
+    }
+
+    /// The lowest selected address.
+    #[inline]
+    pub fn start(&self) -> Address {
+        self.anchor.min(self.cursor)
+    }
+
+    /// The highest selected address.
+    #[inline]
+    pub fn last(&self) -> Address {
+        self.anchor.max(self.cursor)
+    }
+
+    /// The selected addresses as a half-open range.
+    #[inline]
+    pub fn range(&self) -> Range<Address> {
+        self.start()..self.last().saturating_add(1)
+    }
+
+    #[inline]
+    pub fn contains(&self, address: Address) -> bool {
+        (self.start()..=self.last()).contains(&address)
+    }
+
+    /// Whether more than one address is selected.
+    #[inline]
+    pub fn is_multi_byte(&self) -> bool {
+        self.anchor != self.cursor
+    }
+}
@@ -0,0 +1,318 @@
+//! Named copies of memory, which live memory can be compared against.
+
+use std::ops::Range;
+
+use egui::Ui;
+
+use crate::selection::Selection;
+use crate::utilities::AddressRangeExt;
+use crate::{clipboard, memory_cache, utilities, Address, MemoryEditor, MemorySource};
+
+/// Snapshots are kept in memory, so they're limited to this many bytes.
+const MAX_SNAPSHOT_LENGTH: usize = 64 * 1024 * 1024;
+/// The maximum amount of changed spans which are kept by a comparison.
+const MAX_DIFF_SPANS: usize = 100_000;
+
+/// A named copy of a range of memory, see [`MemoryEditor::snapshots`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemorySnapshot {
+    pub name: String,
+    /// The address of the first value.
+    pub start: Address,
+    /// The values starting at `start`, `None` for values which couldn't be read.
+    pub values: Vec<Option<u8>>,
+}
+
+impl MemorySnapshot {
+    /// Take a snapshot of `range`, peeking the values from `source`.
+    pub fn capture<S: MemorySource + ?Sized>(name: impl Into<String>, source: &mut S, range: Range<Address>) -> Self {
+        MemorySnapshot {
+            name: name.into(),
+            start: range.start,
+            values: memory_cache::peek_range(source, range),
+        }
+    }
+
+    /// The addresses covered by this snapshot.
+    pub fn range(&self) -> Range<Address> {
+        self.start..self.start.saturating_add(self.values.len() as Address)
+    }
+
+    /// The value at `address`, or `None` if it's not part of the snapshot or couldn't be read.
+    pub fn get(&self, address: Address) -> Option<u8> {
+        let offset = usize::try_from(address.checked_sub(self.start)?).ok()?;
+        self.values.get(offset).copied().flatten()
+    }
+
+    /// The values as raw bytes, e.g. for writing to a file. Values which couldn't be read are written as `0`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.values.iter().map(|value| value.unwrap_or(0)).collect()
+    }
+
+    /// Whether the `current` value at `address` differs from the snapshot.
+    /// Values which couldn't be read, now or when the snapshot was taken, are never different.
+    pub fn is_changed(&self, address: Address, current: Option<u8>) -> bool {
+        matches!((self.get(address), current), (Some(old), Some(new)) if old != new)
+    }
+
+    /// Compare the snapshot with the memory in `source`, returning the spans of consecutive changed values.
+    pub fn diff<S: MemorySource + ?Sized>(&self, source: &mut S) -> Vec<Range<Address>> {
+        let current = memory_cache::peek_range(source, self.range());
+        let mut spans: Vec<Range<Address>> = Vec::new();
+
+        for (address, &value) in self.range().zip(&current) {
+            if !self.is_changed(address, value) {
+                continue;
+            }
+
+            match spans.last_mut() {
+                Some(span) if span.end == address => span.end += 1,
+                _ => spans.push(address..address + 1),
+            }
+        }
+
+        spans
+    }
+}
+
+/// The result of comparing live memory with a snapshot.
+#[derive(Debug, Clone)]
+pub(crate) struct SnapshotDiff {
+    /// The index of the snapshot in [`MemoryEditor::snapshots`].
+    pub snapshot: usize,
+    /// The spans of consecutive changed values, at most [`MAX_DIFF_SPANS`].
+    pub spans: Vec<Range<Address>>,
+    pub changed_bytes: u64,
+}
+
+impl SnapshotDiff {
+    fn new<S: MemorySource + ?Sized>(index: usize, snapshot: &MemorySnapshot, source: &mut S) -> Self {
+        let spans = snapshot.diff(source);
+
+        SnapshotDiff {
+            snapshot: index,
+            changed_bytes: spans.iter().map(|span| span.length()).sum(),
+            spans: spans.into_iter().take(MAX_DIFF_SPANS).collect(),
+        }
+    }
+}
+
+impl MemoryEditor {
+    /// Draw the snapshot list and the comparison with the selected snapshot underneath a collapsing header.
+    pub(crate) fn draw_snapshots<S: MemorySource + ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        current_address_range: &Range<Address>,
+        source: &mut S,
+    ) {
+        // The snapshots may have been replaced through the API.
+        if let Some(diff) = &self.frame_data.snapshot_diff {
+            if diff.snapshot >= self.snapshots.len() {
+                self.frame_data.snapshot_diff = None;
+            }
+        }
+
+        egui::CollapsingHeader::new("📷 Snapshots")
+            .default_open(false)
+            .show(ui, |ui| {
+                let capture_range = self
+                    .frame_data
+                    .selection
+                    .filter(|selection| selection.is_multi_byte())
+                    .map_or(current_address_range.clone(), |selection| selection.range());
+
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.frame_data.snapshot_name)
+                            .hint_text(format!("Snapshot {}", self.snapshots.len() + 1))
+                            .desired_width(120.0),
+                    );
+
+                    let response = ui.button("Take snapshot").on_hover_text(format!(
+                        "Copy {:#X?}, select a range of addresses to only copy those",
+                        capture_range
+                    ));
+
+                    if response.clicked() {
+                        if capture_range.length() > MAX_SNAPSHOT_LENGTH as Address {
+                            self.frame_data.snapshot_message = Some(format!(
+                                "Snapshots are limited to {} MiB, select a smaller range",
+                                MAX_SNAPSHOT_LENGTH / (1024 * 1024)
+                            ));
+                        } else {
+                            let name = match std::mem::take(&mut self.frame_data.snapshot_name) {
+                                name if name.trim().is_empty() => format!("Snapshot {}", self.snapshots.len() + 1),
+                                name => name,
+                            };
+                            self.snapshots
+                                .push(MemorySnapshot::capture(name, source, capture_range.clone()));
+                            self.frame_data.snapshot_message = None;
+                        }
+                    }
+                });
+
+                if let Some(message) = &self.frame_data.snapshot_message {
+                    ui.colored_label(ui.visuals().error_fg_color, message);
+                }
+
+                self.draw_snapshot_list(ui, source);
+                self.draw_snapshot_diff(ui, current_address_range, source);
+            });
+    }
+
+    /// Draw every snapshot, with buttons to compare, copy or delete it.
+    fn draw_snapshot_list<S: MemorySource + ?Sized>(&mut self, ui: &mut Ui, source: &mut S) {
+        let mut compare = None;
+        let mut remove = None;
+
+        egui::Grid::new("snapshot_grid").striped(true).show(ui, |ui| {
+            for (index, snapshot) in self.snapshots.iter().enumerate() {
+                let is_compared = matches!(&self.frame_data.snapshot_diff, Some(diff) if diff.snapshot == index);
+
+                ui.label(&snapshot.name);
+                ui.label(format!("{:#X?}", snapshot.range()));
+
+                if ui.selectable_label(is_compared, "Compare").clicked() {
+                    compare = Some(index);
+                }
+
+                if ui
+                    .button("Copy")
+                    .on_hover_text("Copy the snapshot to the clipboard, in the format used by Ctrl+C")
+                    .clicked()
+                {
+                    let text = clipboard::format_bytes(
+                        self.options.copy_format,
+                        snapshot.start,
+                        &snapshot.values,
+                        &snapshot.range(),
+                        &self.options,
+                    );
+                    ui.ctx().copy_text(text);
+                }
+
+                if ui.button("Delete").clicked() {
+                    remove = Some(index);
+                }
+
+                ui.end_row();
+            }
+        });
+
+        if let Some(index) = compare {
+            let is_compared = matches!(&self.frame_data.snapshot_diff, Some(diff) if diff.snapshot == index);
+
+            self.frame_data.snapshot_diff = if is_compared {
+                None
+            } else {
+                Some(SnapshotDiff::new(index, &self.snapshots[index], source))
+            };
+        }
+
+        if let Some(index) = remove {
+            self.remove_snapshot(index);
+        }
+    }
+
+    /// Draw the summary of the comparison, the difference navigation, and the list of changed spans.
+    fn draw_snapshot_diff<S: MemorySource + ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        current_address_range: &Range<Address>,
+        source: &mut S,
+    ) {
+        let Some(diff) = &self.frame_data.snapshot_diff else {
+            return;
+        };
+        let snapshot = &self.snapshots[diff.snapshot];
+        let mut jump_to = None;
+        let mut refresh = false;
+
+        ui.separator();
+        ui.label(format!(
+            "{} bytes changed in {} spans since \"{}\"",
+            diff.changed_bytes,
+            diff.spans.len(),
+            snapshot.name
+        ));
+
+        ui.horizontal(|ui| {
+            // Continue from the selection, so that the navigation doesn't get stuck on a span.
+            let cursor = self
+                .frame_data
+                .selection
+                .map(|selection| selection.start())
+                .or(self.frame_data.selected_edit_address);
+
+            if ui.button("⏶ Previous").clicked() {
+                let cursor = cursor.unwrap_or(Address::MAX);
+                let before = diff.spans.partition_point(|span| span.start < cursor);
+                // Wrap around to the last difference.
+                jump_to = before.checked_sub(1).or(diff.spans.len().checked_sub(1));
+            }
+
+            if ui.button("⏷ Next").clicked() {
+                let after = match cursor {
+                    Some(cursor) => diff.spans.partition_point(|span| span.start <= cursor),
+                    None => 0,
+                };
+                // Wrap around to the first difference.
+                jump_to = Some(after).filter(|&i| i < diff.spans.len()).or(Some(0));
+            }
+
+            refresh = ui
+                .button("Refresh")
+                .on_hover_text("Compare the current memory again")
+                .clicked();
+        });
+
+        if refresh {
+            let index = diff.snapshot;
+            self.frame_data.snapshot_diff = Some(SnapshotDiff::new(index, &self.snapshots[index], source));
+        }
+
+        let Some(diff) = &self.frame_data.snapshot_diff else {
+            return;
+        };
+        let address_characters = utilities::address_characters(current_address_range);
+        let row_height = ui.text_style_height(&egui::TextStyle::Body);
+
+        egui::ScrollArea::vertical()
+            .id_salt("snapshot_diff_spans")
+            .max_height(row_height * 6.0)
+            .auto_shrink([false, true])
+            .show_rows(ui, row_height, diff.spans.len(), |ui, rows| {
+                for index in rows {
+                    let span = &diff.spans[index];
+                    let is_selected = self
+                        .frame_data
+                        .selection
+                        .is_some_and(|selection| selection.range() == *span);
+                    let text = format!(
+                        "0x{:0width$X} - 0x{:0width$X} ({} bytes)",
+                        span.start,
+                        span.end - 1,
+                        span.length(),
+                        width = address_characters
+                    );
+
+                    if ui.selectable_label(is_selected, text).clicked() {
+                        jump_to = Some(index);
+                    }
+                }
+            });
+
+        // The snapshot may be of another address range.
+        let span = jump_to.and_then(|index| diff.spans.get(index)).cloned();
+        if let Some(span) = span.filter(|span| current_address_range.contains(&span.start)) {
+            let frame_data = &mut self.frame_data;
+            let span = span.start..span.end.min(current_address_range.end);
+
+            frame_data.set_selected_edit_address(None, current_address_range);
+            frame_data.select(Selection::from_range(&span));
+            frame_data.goto_address_line =
+                Some((span.start - current_address_range.start) / self.options.row_length() as Address);
+        }
+    }
+}
@@ -1,34 +1,566 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+
 use crate::option_data::{DataFormatType, DataPreviewOptions, Endianness};
+use crate::Address;
+
+/// The amount of addresses in a range of addresses, as `Range<u64>` doesn't implement [`ExactSizeIterator`].
+pub trait AddressRangeExt {
+    fn length(&self) -> Address;
+}
+
+impl AddressRangeExt for Range<Address> {
+    #[inline]
+    fn length(&self) -> Address {
+        self.end.saturating_sub(self.start)
+    }
+}
+
+/// The amount of hex digits needed to display the highest address in `address_space`.
+pub fn address_characters(address_space: &Range<Address>) -> usize {
+    // This is janky, but can't think of a better way.
+    format!("{:X}", address_space.end.saturating_sub(1)).chars().count()
+}
+
+/// The character to display in the ASCII sidebar for `value`, any non-printable value is displayed as `.`.
+pub fn ascii_char(value: u8) -> char {
+    if (32..128).contains(&value) {
+        value as char
+    } else {
+        '.'
+    }
+}
+
+/// Combine up to 8 `bytes` into a single value, in the given byte order.
+pub fn bytes_to_word(bytes: &[u8], endianness: Endianness) -> u64 {
+    let fold = |value: u64, &byte: &u8| value << 8 | byte as u64;
+
+    match endianness {
+        Endianness::Big => bytes.iter().fold(0, fold),
+        Endianness::Little => bytes.iter().rev().fold(0, fold),
+    }
+}
+
+/// Split `value` into `width` bytes in the given byte order, the inverse of [`bytes_to_word`].
+pub fn word_to_bytes(value: u64, width: usize, endianness: Endianness) -> Vec<u8> {
+    let mut bytes = value.to_le_bytes()[..width].to_vec();
+    if endianness == Endianness::Big {
+        bytes.reverse();
+    }
+    bytes
+}
+
+/// Encode `bytes` as standard Base64 with padding.
+pub fn to_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut result = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let triple = (chunk[0] as u32) << 16
+            | (chunk.get(1).copied().unwrap_or(0) as u32) << 8
+            | chunk.get(2).copied().unwrap_or(0) as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(ALPHABET[(triple >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+
+    result
+}
+
+/// A decoded value of any [`DataFormatType`].
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+enum Number {
+    Unsigned(u128),
+    Signed(i128),
+    F32(f32),
+    F64(f64),
+}
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Number::Unsigned(value) => value.fmt(f),
+            Number::Signed(value) => value.fmt(f),
+            Number::F32(value) => value.fmt(f),
+            Number::F64(value) => value.fmt(f),
+        }
+    }
+}
+
+/// Decode `bytes` as the number type described by `data_preview`, returns `None` if they're not a valid value, e.g. a
+/// BCD digit above 9 or an unterminated LEB128.
+fn decode_number(data_preview: DataPreviewOptions, bytes: &[u8]) -> Option<Number> {
+    use DataFormatType::*;
+
+    let format = data_preview.selected_data_format;
+
+    match format {
+        Uleb128 => return decode_leb128(bytes).map(|(value, _)| Number::Unsigned(value as u128)),
+        Sleb128 => {
+            return decode_leb128(bytes).map(|(value, bits)| {
+                let unused_bits = 64 - bits.min(64);
+                Number::Signed((((value << unused_bits) as i64) >> unused_bits) as i128)
+            })
+        }
+        _ => {}
+    }
+
+    let bytes = &bytes[..format.bytes_to_read()];
+
+    // Everything else is a fixed width value, which is easiest to handle as little endian.
+    let mut le_bytes = [0; 16];
+    le_bytes[..bytes.len()].copy_from_slice(bytes);
+    if data_preview.selected_endianness == Endianness::Big {
+        le_bytes[..bytes.len()].reverse();
+    }
+    let unsigned = u128::from_le_bytes(le_bytes);
+    let unused_bits = 128 - 8 * bytes.len() as u32;
+    let signed = ((unsigned << unused_bits) as i128) >> unused_bits;
+
+    let number = match format {
+        U8 | U16 | U24 | U32 | U64 | U128 => Number::Unsigned(unsigned),
+        I8 | I16 | I24 | I32 | I64 | I128 => Number::Signed(signed),
+        F16 => Number::F32(f16_to_f32(unsigned as u16)),
+        BF16 => Number::F32(f32::from_bits((unsigned as u32) << 16)),
+        F32 => Number::F32(f32::from_bits(unsigned as u32)),
+        F64 => Number::F64(f64::from_bits(unsigned as u64)),
+        PackedBcd { .. } => {
+            let mut value = 0;
+            for &byte in le_bytes[..bytes.len()].iter().rev() {
+                let (high, low) = (byte >> 4, byte & 0xF);
+                if high > 9 || low > 9 {
+                    return None;
+                }
+                value = value * 100 + (high * 10 + low) as u128;
+            }
+            Number::Unsigned(value)
+        }
+        UnpackedBcd { .. } => {
+            let mut value = 0;
+            for &byte in le_bytes[..bytes.len()].iter().rev() {
+                // The upper nibble is ignored, as it's often used for a zone, e.g. `3` for ASCII digits.
+                if byte & 0xF > 9 {
+                    return None;
+                }
+                value = value * 10 + (byte & 0xF) as u128;
+            }
+            Number::Unsigned(value)
+        }
+        Q16 { fractional_bits } | Q32 { fractional_bits } => {
+            Number::F64(signed as f64 / 2f64.powi(fractional_bits as i32))
+        }
+        Uleb128 | Sleb128 => unreachable!(),
+    };
+
+    Some(number)
+}
+
+/// Decode a LEB128 value of at most 10 bytes, returning its value and the amount of bits that were encoded.
+fn decode_leb128(bytes: &[u8]) -> Option<(u64, u32)> {
+    let mut value = 0u64;
+
+    for (i, byte) in bytes.iter().take(10).enumerate() {
+        value |= ((byte & 0x7F) as u64).checked_shl(7 * i as u32).unwrap_or(0);
+
+        if byte & 0x80 == 0 {
+            return Some((value, 7 * (i as u32 + 1)));
+        }
+    }
+
+    None
+}
 
 /// Turn a provided slice into a decimal [`String`] representing it's value, interpretation is based on the provided
 /// [`crate::option_data::DataPreviewOptions`].
 ///
 /// The provided `bytes` slice is expected to have the appropriate amount of bytes, or else the function will panic.
+/// Invalid values, like a BCD digit above 9, are shown as `Invalid`.
 pub fn slice_to_decimal_string(data_preview: DataPreviewOptions, bytes: &[u8]) -> String {
-    match data_preview.selected_endianness {
-        Endianness::Big => match data_preview.selected_data_format {
-            DataFormatType::U8 => u8::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::U16 => u16::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::U32 => u32::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::U64 => u64::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I8 => i8::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I16 => i16::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I32 => i32::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I64 => i64::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::F32 => f32::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::F64 => f64::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-        },
-        Endianness::Little => match data_preview.selected_data_format {
-            DataFormatType::U8 => u8::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::U16 => u16::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::U32 => u32::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::U64 => u64::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I8 => i8::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I16 => i16::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I32 => i32::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I64 => i64::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::F32 => f32::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::F64 => f64::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-        },
+    decode_number(data_preview, bytes).map_or_else(|| "Invalid".to_string(), |number| number.to_string())
+}
+
+/// Like [`slice_to_decimal_string`], but very large and very small floats are written in scientific notation, so that
+/// the value fits in [`DataFormatType::max_display_length`].
+pub fn slice_to_cell_string(data_preview: DataPreviewOptions, bytes: &[u8]) -> String {
+    let is_compact = |value: f64| value == 0.0 || !value.is_finite() || (1e-4..1e7).contains(&value.abs());
+
+    match decode_number(data_preview, bytes) {
+        Some(Number::F32(value)) if !is_compact(value as f64) => format!("{:e}", value),
+        Some(Number::F64(value)) if !is_compact(value) => format!("{:e}", value),
+        Some(number) => number.to_string(),
+        None => "Invalid".to_string(),
+    }
+}
+
+/// Parse a decimal `text` into the bytes of the number type described by `data_preview`, the inverse of
+/// [`slice_to_decimal_string`].
+///
+/// Variable length formats are encoded in as few bytes as possible.
+pub fn decimal_string_to_bytes(data_preview: DataPreviewOptions, text: &str) -> Result<Vec<u8>, String> {
+    use DataFormatType::*;
+
+    fn parse<T: std::str::FromStr>(text: &str) -> Result<T, String>
+    where
+        T::Err: std::fmt::Display,
+    {
+        text.trim().parse::<T>().map_err(|e| e.to_string())
+    }
+    fn check_range<T: PartialOrd>(value: T, range: std::ops::RangeInclusive<T>) -> Result<T, String> {
+        if value < *range.start() {
+            Err("number too small to fit in target type".to_string())
+        } else if value > *range.end() {
+            Err("number too large to fit in target type".to_string())
+        } else if !range.contains(&value) {
+            // Only NaN is neither smaller, larger, nor inside the range.
+            Err("not a number".to_string())
+        } else {
+            Ok(value)
+        }
+    }
+    // Values too large for the format would otherwise be written as infinity.
+    fn check_finite<T>(text: &str, value: T, is_infinite: bool) -> Result<T, String> {
+        let magnitude = text.trim().trim_start_matches(['+', '-']).to_ascii_lowercase();
+        if is_infinite && !matches!(magnitude.as_str(), "inf" | "infinity") {
+            Err("number too large to fit in target type".to_string())
+        } else {
+            Ok(value)
+        }
+    }
+
+    let format = data_preview.selected_data_format;
+    let mut bytes = match format {
+        U8 => parse::<u8>(text)?.to_le_bytes().to_vec(),
+        U16 => parse::<u16>(text)?.to_le_bytes().to_vec(),
+        U24 => check_range(parse::<u32>(text)?, 0..=0xFF_FFFF)?.to_le_bytes()[..3].to_vec(),
+        U32 => parse::<u32>(text)?.to_le_bytes().to_vec(),
+        U64 => parse::<u64>(text)?.to_le_bytes().to_vec(),
+        U128 => parse::<u128>(text)?.to_le_bytes().to_vec(),
+        I8 => parse::<i8>(text)?.to_le_bytes().to_vec(),
+        I16 => parse::<i16>(text)?.to_le_bytes().to_vec(),
+        I24 => check_range(parse::<i32>(text)?, -0x80_0000..=0x7F_FFFF)?.to_le_bytes()[..3].to_vec(),
+        I32 => parse::<i32>(text)?.to_le_bytes().to_vec(),
+        I64 => parse::<i64>(text)?.to_le_bytes().to_vec(),
+        I128 => parse::<i128>(text)?.to_le_bytes().to_vec(),
+        F16 => {
+            let bits = f32_to_f16(parse::<f32>(text)?);
+            check_finite(text, bits, f16_to_f32(bits).is_infinite())?
+                .to_le_bytes()
+                .to_vec()
+        }
+        BF16 => {
+            let bits = f32_to_bf16(parse::<f32>(text)?);
+            let value = f32::from_bits((bits as u32) << 16);
+            check_finite(text, bits, value.is_infinite())?.to_le_bytes().to_vec()
+        }
+        F32 => {
+            let value = parse::<f32>(text)?;
+            check_finite(text, value, value.is_infinite())?.to_le_bytes().to_vec()
+        }
+        F64 => {
+            let value = parse::<f64>(text)?;
+            check_finite(text, value, value.is_infinite())?.to_le_bytes().to_vec()
+        }
+        PackedBcd { .. } | UnpackedBcd { .. } => {
+            let bytes = format.bytes_to_read() as u32;
+            let digits_per_byte = if matches!(format, PackedBcd { .. }) { 2 } else { 1 };
+            let digits = bytes * digits_per_byte;
+            let mut value = check_range(parse::<u128>(text)?, 0..=10u128.saturating_pow(digits) - 1)?;
+
+            (0..bytes)
+                .map(|_| {
+                    let byte = if digits_per_byte == 2 {
+                        ((value % 10) | (value / 10 % 10) << 4) as u8
+                    } else {
+                        (value % 10) as u8
+                    };
+                    value /= 10u128.pow(digits_per_byte);
+                    byte
+                })
+                .collect()
+        }
+        Q16 { fractional_bits } => {
+            let scaled = (parse::<f64>(text)? * 2f64.powi(fractional_bits as i32)).round();
+            (check_range(scaled, i16::MIN as f64..=i16::MAX as f64)? as i16)
+                .to_le_bytes()
+                .to_vec()
+        }
+        Q32 { fractional_bits } => {
+            let scaled = (parse::<f64>(text)? * 2f64.powi(fractional_bits as i32)).round();
+            (check_range(scaled, i32::MIN as f64..=i32::MAX as f64)? as i32)
+                .to_le_bytes()
+                .to_vec()
+        }
+        // The byte order of LEB128 is fixed, so return before the endianness is applied.
+        Uleb128 => return Ok(encode_leb128(parse::<u64>(text)? as i128, false)),
+        Sleb128 => return Ok(encode_leb128(parse::<i64>(text)? as i128, true)),
+    };
+
+    if data_preview.selected_endianness == Endianness::Big {
+        bytes.reverse();
+    }
+
+    Ok(bytes)
+}
+
+/// Encode `value` as LEB128, in as few bytes as possible.
+fn encode_leb128(mut value: i128, signed: bool) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+
+        // Signed values are done once the remaining bits, including the sign bit of this byte, are all the same.
+        let done = if signed {
+            (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0)
+        } else {
+            value == 0
+        };
+
+        if done {
+            bytes.push(byte);
+            return bytes;
+        }
+
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Convert the bits of an IEEE 754 half precision float to an `f32`, which can represent every value exactly.
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits as u32) & 0x8000) << 16;
+    let exponent = (bits >> 10) & 0x1F;
+    let mantissa = (bits & 0x3FF) as u32;
+
+    let magnitude = match exponent {
+        // Zero and subnormals.
+        0 => mantissa as f32 * 2f32.powi(-24),
+        // Infinity and NaN.
+        0x1F => f32::from_bits(0x7F80_0000 | mantissa << 13),
+        _ => f32::from_bits((exponent as u32 + 127 - 15) << 23 | mantissa << 13),
+    };
+
+    f32::from_bits(magnitude.to_bits() | sign)
+}
+
+/// Convert an `f32` to the bits of the nearest IEEE 754 half precision float, rounding ties to even.
+fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xFF) as i32;
+    let mantissa = bits & 0x7F_FFFF;
+
+    if exponent == 0xFF {
+        return sign | 0x7C00 | if mantissa != 0 { 0x200 } else { 0 };
+    }
+
+    let half_exponent = exponent - 127 + 15;
+    let round = |value: u32, shift: u32| {
+        let truncated = value >> shift;
+        let remainder = value & ((1 << shift) - 1);
+        let halfway = 1 << (shift - 1);
+        truncated + (remainder > halfway || (remainder == halfway && truncated & 1 == 1)) as u32
+    };
+
+    match half_exponent {
+        0x1F.. => sign | 0x7C00,
+        // Too small for even the smallest subnormal.
+        ..-10 => sign,
+        // Subnormals, which includes the implicit leading bit in the mantissa.
+        -10..=0 => sign | round(mantissa | 0x80_0000, (14 - half_exponent) as u32) as u16,
+        // A carry out of the mantissa correctly increments the exponent, up to infinity.
+        _ => sign | round((half_exponent as u32) << 23 | mantissa, 13) as u16,
+    }
+}
+
+/// Convert an `f32` to the bits of the nearest `bfloat16`, rounding ties to even.
+fn f32_to_bf16(value: f32) -> u16 {
+    let bits = value.to_bits();
+
+    if value.is_nan() {
+        // Keep it a NaN, even if the payload was only in the lower bits.
+        return (bits >> 16) as u16 | 0x40;
+    }
+
+    (bits.wrapping_add(0x7FFF + ((bits >> 16) & 1)) >> 16) as u16
+}
+
+/// Compare two values of the number type described by `data_preview`, returns `None` if either is a `NaN` or invalid.
+///
+/// Both slices are expected to have the appropriate amount of bytes, or else the function will panic.
+pub fn compare_values(data_preview: DataPreviewOptions, a: &[u8], b: &[u8]) -> Option<Ordering> {
+    decode_number(data_preview, a)?.partial_cmp(&decode_number(data_preview, b)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(format: DataFormatType) -> DataPreviewOptions {
+        DataPreviewOptions {
+            selected_endianness: Endianness::Little,
+            selected_data_format: format,
+        }
+    }
+
+    #[test]
+    fn f16_round_trip() {
+        for bits in 0..=u16::MAX {
+            let value = f16_to_f32(bits);
+
+            if value.is_nan() {
+                // Only whether it's a NaN is kept, not its payload.
+                assert_eq!(f32_to_f16(value) & 0x7C00, 0x7C00, "{:#06X}", bits);
+                assert_ne!(f32_to_f16(value) & 0x3FF, 0, "{:#06X}", bits);
+            } else {
+                assert_eq!(f32_to_f16(value), bits, "{:#06X}", bits);
+            }
+        }
+    }
+
+    #[test]
+    fn f16_rounding() {
+        assert_eq!(f32_to_f16(65504.0), 0x7BFF);
+        assert_eq!(f32_to_f16(65520.0), 0x7C00);
+        // Halfway between 1.0 and the next value, rounded to the even mantissa.
+        assert_eq!(f32_to_f16(1.0 + 2f32.powi(-11)), 0x3C00);
+        assert_eq!(f32_to_f16(2f32.powi(-24)), 0x0001);
+        assert_eq!(f32_to_f16(2f32.powi(-26)), 0x0000);
+        assert_eq!(f32_to_f16(-0.0), 0x8000);
+    }
+
+    #[test]
+    fn bf16_rounding() {
+        assert_eq!(f32_to_bf16(1.0), 0x3F80);
+        assert_eq!(f32_to_bf16(-2.0), 0xC000);
+        assert_eq!(f32_to_bf16(f32::from_bits(0x3F80_8000)), 0x3F80);
+        assert_eq!(f32_to_bf16(f32::from_bits(0x3F81_8000)), 0x3F82);
+        assert_eq!(f32_to_bf16(f32::INFINITY), 0x7F80);
+        // A NaN with its payload in the lower bits stays a NaN.
+        assert!(f32::from_bits((f32_to_bf16(f32::from_bits(0x7F80_0001)) as u32) << 16).is_nan());
+    }
+
+    #[test]
+    fn unsigned_leb128() {
+        let cases: [(u64, &[u8]); 5] = [
+            (0, &[0x00]),
+            (127, &[0x7F]),
+            (128, &[0x80, 0x01]),
+            (624_485, &[0xE5, 0x8E, 0x26]),
+            (u64::MAX, &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]),
+        ];
+
+        for (value, bytes) in cases {
+            assert_eq!(encode_leb128(value as i128, false), bytes);
+            assert_eq!(
+                decimal_string_to_bytes(options(DataFormatType::Uleb128), &value.to_string()).unwrap(),
+                bytes
+            );
+            assert_eq!(
+                slice_to_decimal_string(options(DataFormatType::Uleb128), bytes),
+                value.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn signed_leb128() {
+        let cases: [(i64, &[u8]); 8] = [
+            (0, &[0x00]),
+            (-1, &[0x7F]),
+            (63, &[0x3F]),
+            (64, &[0xC0, 0x00]),
+            (-64, &[0x40]),
+            (-65, &[0xBF, 0x7F]),
+            (-123_456, &[0xC0, 0xBB, 0x78]),
+            (i64::MIN, &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7F]),
+        ];
+
+        for (value, bytes) in cases {
+            assert_eq!(encode_leb128(value as i128, true), bytes);
+            assert_eq!(
+                decimal_string_to_bytes(options(DataFormatType::Sleb128), &value.to_string()).unwrap(),
+                bytes
+            );
+            assert_eq!(
+                slice_to_decimal_string(options(DataFormatType::Sleb128), bytes),
+                value.to_string()
+            );
+        }
+
+        let max = decimal_string_to_bytes(options(DataFormatType::Sleb128), &i64::MAX.to_string()).unwrap();
+        assert_eq!(
+            slice_to_decimal_string(options(DataFormatType::Sleb128), &max),
+            i64::MAX.to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_leb128() {
+        // Unterminated, and longer than the 10 bytes of a 64 bit value.
+        assert_eq!(decode_leb128(&[0x80, 0x80]), None);
+        assert_eq!(decode_leb128(&[0x80; 11]), None);
+        assert_eq!(
+            slice_to_decimal_string(options(DataFormatType::Uleb128), &[0xFF]),
+            "Invalid"
+        );
+        assert!(decimal_string_to_bytes(options(DataFormatType::Uleb128), "-1").is_err());
+    }
+
+    #[test]
+    fn packed_bcd() {
+        let format = options(DataFormatType::PackedBcd { bytes: 2 });
+
+        assert_eq!(decimal_string_to_bytes(format, "1234").unwrap(), [0x34, 0x12]);
+        assert_eq!(decimal_string_to_bytes(format, "9999").unwrap(), [0x99, 0x99]);
+        assert!(decimal_string_to_bytes(format, "10000").is_err());
+        assert_eq!(slice_to_decimal_string(format, &[0x34, 0x12]), "1234");
+        // Either nibble above 9 isn't a digit.
+        assert_eq!(slice_to_decimal_string(format, &[0x3A, 0x12]), "Invalid");
+        assert_eq!(slice_to_decimal_string(format, &[0x34, 0xA2]), "Invalid");
+    }
+
+    #[test]
+    fn unpacked_bcd() {
+        let format = options(DataFormatType::UnpackedBcd { bytes: 2 });
+
+        assert_eq!(decimal_string_to_bytes(format, "12").unwrap(), [0x02, 0x01]);
+        assert!(decimal_string_to_bytes(format, "100").is_err());
+        // The upper nibble is a zone, and ignored.
+        assert_eq!(slice_to_decimal_string(format, &[0x32, 0x31]), "12");
+        assert_eq!(slice_to_decimal_string(format, &[0x0A, 0x01]), "Invalid");
+    }
+
+    #[test]
+    fn fixed_point_limits() {
+        let q16 = options(DataFormatType::Q16 { fractional_bits: 8 });
+
+        assert_eq!(decimal_string_to_bytes(q16, "127.99609375").unwrap(), [0xFF, 0x7F]);
+        assert_eq!(decimal_string_to_bytes(q16, "-128").unwrap(), [0x00, 0x80]);
+        assert!(decimal_string_to_bytes(q16, "128").is_err());
+        assert!(decimal_string_to_bytes(q16, "-128.01").is_err());
+        assert!(decimal_string_to_bytes(q16, "NaN").is_err());
+        assert_eq!(slice_to_decimal_string(q16, &[0xFF, 0x7F]), "127.99609375");
+        assert_eq!(slice_to_decimal_string(q16, &[0x00, 0x80]), "-128");
+
+        let q32 = options(DataFormatType::Q32 { fractional_bits: 16 });
+
+        assert_eq!(
+            decimal_string_to_bytes(q32, "32767.9999847412109375").unwrap(),
+            [0xFF, 0xFF, 0xFF, 0x7F]
+        );
+        assert_eq!(
+            decimal_string_to_bytes(q32, "-32768").unwrap(),
+            [0x00, 0x00, 0x00, 0x80]
+        );
+        assert!(decimal_string_to_bytes(q32, "32768").is_err());
+        assert!(decimal_string_to_bytes(q32, "-32768.001").is_err());
+        assert_eq!(slice_to_decimal_string(q32, &[0x00, 0x00, 0x00, 0x80]), "-32768");
     }
 }